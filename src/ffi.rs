@@ -5,7 +5,10 @@
 //! Layer 3: C FFI — `extern "C"` functions wrapping [`Page`](crate::Page).
 
 use crate::page::Page;
-use crate::types::{InputFile, PageError, PageOptions};
+use crate::types::{
+    DiffOptions, EmulationSettings, InputFile, Orientation, PageError, PageOptions, PageRange,
+    PdfOptions,
+};
 
 const PAGE_OK: i32 = 0;
 const PAGE_ERR_INIT: i32 = 1;
@@ -17,6 +20,8 @@ const PAGE_ERR_CHANNEL: i32 = 6;
 const PAGE_ERR_NULL_PTR: i32 = 7;
 const PAGE_ERR_NO_PAGE: i32 = 8;
 const PAGE_ERR_SELECTOR: i32 = 9;
+const PAGE_ERR_PDF: i32 = 10;
+const PAGE_ERR_NAVIGATION: i32 = 11;
 
 fn error_code(e: &PageError) -> i32 {
     match e {
@@ -25,9 +30,14 @@ fn error_code(e: &PageError) -> i32 {
         PageError::Timeout => PAGE_ERR_TIMEOUT,
         PageError::JsError(_) => PAGE_ERR_JS,
         PageError::ScreenshotFailed(_) => PAGE_ERR_SCREENSHOT,
+        PageError::PdfFailed(_) => PAGE_ERR_PDF,
         PageError::ChannelClosed => PAGE_ERR_CHANNEL,
         PageError::NoPage => PAGE_ERR_NO_PAGE,
         PageError::SelectorNotFound(_) => PAGE_ERR_SELECTOR,
+        PageError::ResponseBodyNotFound(_) => PAGE_ERR_NO_PAGE,
+        PageError::CannotDiscardActivePage => PAGE_ERR_NO_PAGE,
+        PageError::ElementNotInteractable(_) => PAGE_ERR_SELECTOR,
+        PageError::Navigation { .. } => PAGE_ERR_NAVIGATION,
     }
 }
 
@@ -39,11 +49,21 @@ fn error_code(e: &PageError) -> i32 {
 /// The caller must free it with `page_free()`.
 ///
 /// `user_agent` may be NULL to use the default User-Agent.
+/// `basic_auth_user`/`basic_auth_pass` may both be NULL to send no HTTP basic auth.
+///
+/// `PageOptions::extra_headers` has no construction-time FFI binding — set it
+/// afterwards via `page_set_extra_http_headers()`. `capture_bodies` has no
+/// construction-time FFI binding either — toggle it afterwards via
+/// `page_capture_response_bodies()`. `max_body_capture_bytes`/`max_live_pages` have no
+/// FFI binding at all — use the Rust `Page`/`PageEngine` API directly if you need those.
+///
+/// `device_scale_factor` of `0.0` is treated as the default (`1.0`).
 ///
 /// # Safety
 ///
 /// The returned pointer must be freed with `page_free()`.
-/// `user_agent`, if not NULL, must be a valid C string.
+/// `user_agent`, `basic_auth_user`, and `basic_auth_pass`, if not NULL, must be valid
+/// C strings.
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn page_new(
     width: u32,
@@ -52,6 +72,9 @@ pub unsafe extern "C" fn page_new(
     wait: f64,
     fullpage: i32,
     user_agent: *const std::ffi::c_char,
+    basic_auth_user: *const std::ffi::c_char,
+    basic_auth_pass: *const std::ffi::c_char,
+    device_scale_factor: f32,
 ) -> *mut Page {
     let ua = if user_agent.is_null() {
         None
@@ -61,6 +84,17 @@ pub unsafe extern "C" fn page_new(
             Err(_) => None,
         }
     };
+    let basic_auth = if basic_auth_user.is_null() || basic_auth_pass.is_null() {
+        None
+    } else {
+        match (
+            unsafe { std::ffi::CStr::from_ptr(basic_auth_user) }.to_str(),
+            unsafe { std::ffi::CStr::from_ptr(basic_auth_pass) }.to_str(),
+        ) {
+            (Ok(user), Ok(pass)) => Some((user.to_string(), pass.to_string())),
+            _ => None,
+        }
+    };
     let options = PageOptions {
         width,
         height,
@@ -68,6 +102,19 @@ pub unsafe extern "C" fn page_new(
         wait,
         fullpage: fullpage != 0,
         user_agent: ua,
+        request_rules: Vec::new(),
+        cookies: Vec::new(),
+        extra_headers: Vec::new(),
+        basic_auth,
+        init_scripts: Vec::new(),
+        capture_bodies: false,
+        max_body_capture_bytes: PageOptions::default().max_body_capture_bytes,
+        device_scale_factor: if device_scale_factor > 0.0 {
+            device_scale_factor
+        } else {
+            1.0
+        },
+        max_live_pages: None,
     };
     match Page::new(options) {
         Ok(p) => Box::into_raw(Box::new(p)),
@@ -168,6 +215,44 @@ pub unsafe extern "C" fn page_evaluate(
     }
 }
 
+/// Evaluate JavaScript in a sandboxed realm isolated from page tampering. See
+/// `PageEngine::evaluate_isolated`. Same output convention as `page_evaluate()`.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_evaluate_isolated(
+    page: *mut Page,
+    script: *const std::ffi::c_char,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || script.is_null() || out_json.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let script_str = match unsafe { std::ffi::CStr::from_ptr(script) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.evaluate_isolated(script_str) {
+        Ok(json) => match std::ffi::CString::new(json) {
+            Ok(cstr) => {
+                let len = cstr.as_bytes().len();
+                let ptr = cstr.into_raw();
+                unsafe {
+                    *out_json = ptr;
+                    *out_len = len;
+                }
+                PAGE_OK
+            }
+            Err(_) => PAGE_ERR_JS,
+        },
+        Err(e) => error_code(&e),
+    }
+}
+
 /// Take a screenshot. Returns PNG bytes.
 ///
 /// On success, `*out_data` and `*out_len` are set. Free with `page_buffer_free()`.
@@ -230,553 +315,2585 @@ pub unsafe extern "C" fn page_screenshot_fullpage(
     }
 }
 
-/// Capture the page HTML.
+/// Take a screenshot cropped to `(x, y, width, height)` in device pixels. Returns PNG
+/// bytes.
 ///
-/// On success, `*out_html` and `*out_len` are set. Free with `page_string_free()`.
+/// On success, `*out_data` and `*out_len` are set. Free with `page_buffer_free()`.
 ///
 /// # Safety
 ///
 /// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_html(
+pub unsafe extern "C" fn page_screenshot_clip(
     page: *mut Page,
-    out_html: *mut *mut std::ffi::c_char,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    out_data: *mut *mut u8,
     out_len: *mut usize,
 ) -> i32 {
-    if page.is_null() || out_html.is_null() || out_len.is_null() {
+    if page.is_null() || out_data.is_null() || out_len.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    match page.html() {
-        Ok(html) => match std::ffi::CString::new(html) {
-            Ok(cstr) => {
-                let len = cstr.as_bytes().len();
-                let ptr = cstr.into_raw();
-                unsafe {
-                    *out_html = ptr;
-                    *out_len = len;
-                }
-                PAGE_OK
+    match page.screenshot_clip(x, y, width, height) {
+        Ok(png_bytes) => {
+            let boxed = png_bytes.into_boxed_slice();
+            let len = boxed.len();
+            let ptr = Box::into_raw(boxed) as *mut u8;
+            unsafe {
+                *out_data = ptr;
+                *out_len = len;
             }
-            Err(_) => PAGE_ERR_JS,
-        },
+            PAGE_OK
+        }
         Err(e) => error_code(&e),
     }
 }
 
-// -- Page info --
-
-/// Get the current page URL.
+/// Take a screenshot cropped to the bounding rect of the first element matching
+/// `selector`. Returns PNG bytes.
+///
+/// On success, `*out_data` and `*out_len` are set. Free with `page_buffer_free()`.
 ///
 /// # Safety
 ///
 /// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_url(
+pub unsafe extern "C" fn page_screenshot_element(
     page: *mut Page,
-    out_url: *mut *mut std::ffi::c_char,
+    selector: *const std::ffi::c_char,
+    out_data: *mut *mut u8,
     out_len: *mut usize,
 ) -> i32 {
-    if page.is_null() || out_url.is_null() || out_len.is_null() {
+    if page.is_null() || selector.is_null() || out_data.is_null() || out_len.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    match page.url() {
-        Some(url_str) => match std::ffi::CString::new(url_str) {
-            Ok(cstr) => {
-                let len = cstr.as_bytes().len();
-                let ptr = cstr.into_raw();
-                unsafe {
-                    *out_url = ptr;
-                    *out_len = len;
-                }
-                PAGE_OK
+    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.screenshot_element(sel) {
+        Ok(png_bytes) => {
+            let boxed = png_bytes.into_boxed_slice();
+            let len = boxed.len();
+            let ptr = Box::into_raw(boxed) as *mut u8;
+            unsafe {
+                *out_data = ptr;
+                *out_len = len;
             }
-            Err(_) => PAGE_ERR_JS,
-        },
-        None => PAGE_ERR_NO_PAGE,
+            PAGE_OK
+        }
+        Err(e) => error_code(&e),
     }
 }
 
-/// Get the current page title.
+/// Take a screenshot with full control over output format, an optional clip region,
+/// and background handling. `format` is `0` = PNG, `1` = JPEG, `2` = WebP (anything
+/// else falls back to PNG); `quality` (`0..=100`) is ignored for PNG. Pass `has_clip
+/// == 0` to capture the full viewport; otherwise `clip_x`/`clip_y`/`clip_width`/
+/// `clip_height` crop it. `omit_background != 0` captures with a transparent
+/// background instead of the page's own — see `PageEngine::screenshot_with` for the
+/// caveats that implies. Returns bytes in the requested format.
+///
+/// On success, `*out_data` and `*out_len` are set. Free with `page_buffer_free()`.
 ///
 /// # Safety
 ///
 /// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_title(
+pub unsafe extern "C" fn page_screenshot_with(
     page: *mut Page,
-    out_title: *mut *mut std::ffi::c_char,
+    format: i32,
+    quality: u8,
+    has_clip: i32,
+    clip_x: u32,
+    clip_y: u32,
+    clip_width: u32,
+    clip_height: u32,
+    omit_background: i32,
+    out_data: *mut *mut u8,
     out_len: *mut usize,
 ) -> i32 {
-    if page.is_null() || out_title.is_null() || out_len.is_null() {
+    if page.is_null() || out_data.is_null() || out_len.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    match page.title() {
-        Some(title_str) => match std::ffi::CString::new(title_str) {
-            Ok(cstr) => {
-                let len = cstr.as_bytes().len();
-                let ptr = cstr.into_raw();
-                unsafe {
-                    *out_title = ptr;
-                    *out_len = len;
-                }
-                PAGE_OK
+    let format = match format {
+        1 => crate::types::ScreenshotFormat::Jpeg { quality },
+        2 => crate::types::ScreenshotFormat::WebP { quality },
+        _ => crate::types::ScreenshotFormat::Png,
+    };
+    let clip = (has_clip != 0).then_some(crate::types::ClipRect {
+        x: clip_x,
+        y: clip_y,
+        width: clip_width,
+        height: clip_height,
+    });
+    let opts = crate::types::ScreenshotOptions {
+        format,
+        clip,
+        omit_background: omit_background != 0,
+    };
+    match page.screenshot_with(opts) {
+        Ok(bytes) => {
+            let boxed = bytes.into_boxed_slice();
+            let len = boxed.len();
+            let ptr = Box::into_raw(boxed) as *mut u8;
+            unsafe {
+                *out_data = ptr;
+                *out_len = len;
             }
-            Err(_) => PAGE_ERR_JS,
-        },
-        None => PAGE_ERR_NO_PAGE,
+            PAGE_OK
+        }
+        Err(e) => error_code(&e),
     }
 }
 
-// -- Events (JSON) --
-
-/// Get console messages as a JSON array.
+/// Compare two PNG screenshots via the `pixelmatch` algorithm. `threshold` is the
+/// fraction of the maximum YIQ color delta above which a pixel counts as differing
+/// (pass `0.1` to match the library default). On success, `*out_diff_pixels` and
+/// `*out_total_pixels` are set, and `*out_data`/`*out_len` receive PNG bytes
+/// visualizing the diff (unchanged pixels dimmed, differing pixels red) — free with
+/// `page_buffer_free()`. Returns `PAGE_ERR_SCREENSHOT` if the images can't be
+/// decoded or their dimensions don't match.
 ///
 /// # Safety
 ///
 /// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_console_messages(
-    page: *mut Page,
-    out_json: *mut *mut std::ffi::c_char,
+pub unsafe extern "C" fn page_compare_screenshots(
+    baseline_data: *const u8,
+    baseline_len: usize,
+    actual_data: *const u8,
+    actual_len: usize,
+    threshold: f64,
+    out_diff_pixels: *mut usize,
+    out_total_pixels: *mut usize,
+    out_data: *mut *mut u8,
     out_len: *mut usize,
 ) -> i32 {
-    if page.is_null() || out_json.is_null() || out_len.is_null() {
+    if baseline_data.is_null()
+        || actual_data.is_null()
+        || out_diff_pixels.is_null()
+        || out_total_pixels.is_null()
+        || out_data.is_null()
+        || out_len.is_null()
+    {
         return PAGE_ERR_NULL_PTR;
     }
-    let page = unsafe { &*page };
-    let msgs = page.console_messages();
-    let json = serde_json::to_string(&msgs).unwrap_or_else(|_| "[]".to_string());
-    match std::ffi::CString::new(json) {
-        Ok(cstr) => {
-            let len = cstr.as_bytes().len();
-            let ptr = cstr.into_raw();
+    let baseline = unsafe { std::slice::from_raw_parts(baseline_data, baseline_len) };
+    let actual = unsafe { std::slice::from_raw_parts(actual_data, actual_len) };
+    match crate::engine::compare_screenshots(baseline, actual, DiffOptions { threshold }) {
+        Ok(diff) => {
+            let boxed = diff.diff_image.into_boxed_slice();
+            let len = boxed.len();
+            let ptr = Box::into_raw(boxed) as *mut u8;
             unsafe {
-                *out_json = ptr;
+                *out_diff_pixels = diff.diff_pixels;
+                *out_total_pixels = diff.total_pixels;
+                *out_data = ptr;
                 *out_len = len;
             }
             PAGE_OK
         }
-        Err(_) => PAGE_ERR_JS,
+        Err(e) => error_code(&e),
     }
 }
 
-/// Get network requests as a JSON array.
+/// Capture the current viewport and compare it against `baseline_data`/`baseline_len`
+/// (PNG bytes) via [`page_compare_screenshots`], using the default threshold (`0.1`).
+/// Outputs are the same as [`page_compare_screenshots`].
 ///
 /// # Safety
 ///
 /// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_network_requests(
+pub unsafe extern "C" fn page_screenshot_diff(
     page: *mut Page,
-    out_json: *mut *mut std::ffi::c_char,
+    baseline_data: *const u8,
+    baseline_len: usize,
+    out_diff_pixels: *mut usize,
+    out_total_pixels: *mut usize,
+    out_data: *mut *mut u8,
     out_len: *mut usize,
 ) -> i32 {
-    if page.is_null() || out_json.is_null() || out_len.is_null() {
+    if page.is_null()
+        || baseline_data.is_null()
+        || out_diff_pixels.is_null()
+        || out_total_pixels.is_null()
+        || out_data.is_null()
+        || out_len.is_null()
+    {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    let reqs = page.network_requests();
-    let json = serde_json::to_string(&reqs).unwrap_or_else(|_| "[]".to_string());
-    match std::ffi::CString::new(json) {
-        Ok(cstr) => {
-            let len = cstr.as_bytes().len();
-            let ptr = cstr.into_raw();
+    let baseline = unsafe { std::slice::from_raw_parts(baseline_data, baseline_len) };
+    match page.screenshot_diff(baseline) {
+        Ok(diff) => {
+            let boxed = diff.diff_image.into_boxed_slice();
+            let len = boxed.len();
+            let ptr = Box::into_raw(boxed) as *mut u8;
             unsafe {
-                *out_json = ptr;
+                *out_diff_pixels = diff.diff_pixels;
+                *out_total_pixels = diff.total_pixels;
+                *out_data = ptr;
                 *out_len = len;
             }
             PAGE_OK
         }
-        Err(_) => PAGE_ERR_JS,
+        Err(e) => error_code(&e),
     }
 }
 
-// -- Wait FFI --
-
-/// Wait for a CSS selector to match an element.
+/// Alias for `page_screenshot_element()`: screenshot cropped to the bounding rect of
+/// the first element matching `selector`, scrolling it into view first.
+///
+/// On success, `*out_data` and `*out_len` are set. Free with `page_buffer_free()`.
 ///
 /// # Safety
 ///
-/// `page` and `selector` must be valid pointers.
+/// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_wait_for_selector(
+pub unsafe extern "C" fn page_screenshot_selector(
     page: *mut Page,
     selector: *const std::ffi::c_char,
-    timeout_secs: u64,
+    out_data: *mut *mut u8,
+    out_len: *mut usize,
 ) -> i32 {
-    if page.is_null() || selector.is_null() {
-        return PAGE_ERR_NULL_PTR;
-    }
-    let page = unsafe { &*page };
-    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
-        Ok(s) => s,
-        Err(_) => return PAGE_ERR_JS,
-    };
-    match page.wait_for_selector(sel, timeout_secs) {
-        Ok(()) => PAGE_OK,
-        Err(e) => error_code(&e),
+    unsafe { page_screenshot_element(page, selector, out_data, out_len) }
+}
+
+/// Parse a WebDriver-style page range string, e.g. `"1,3-8"`, into [`PageRange`]s.
+fn parse_page_ranges(spec: &str) -> Option<Vec<PageRange>> {
+    let ranges: Vec<PageRange> = spec
+        .split(',')
+        .filter_map(|part| {
+            let part = part.trim();
+            if part.is_empty() {
+                return None;
+            }
+            match part.split_once('-') {
+                Some((start, end)) => Some(PageRange::Range(
+                    start.trim().parse().ok()?,
+                    end.trim().parse().ok()?,
+                )),
+                None => Some(PageRange::Single(part.parse().ok()?)),
+            }
+        })
+        .collect();
+    if ranges.is_empty() {
+        None
+    } else {
+        Some(ranges)
     }
 }
 
-/// Wait for a JS expression to evaluate to a truthy value.
+/// Render the current viewport to a PDF, modeled on the WebDriver print parameters:
+/// paper size and margins in inches, `landscape` (0/1), `scale` factor, `background`
+/// (0/1, whether to render CSS backgrounds), and an optional `page_ranges` string like
+/// `"1,3-8"` (NULL prints everything). Servo has no paginated print path, so the PDF
+/// has a single page holding the current viewport render.
+///
+/// On success, `*out_data` and `*out_len` are set. Free with `page_buffer_free()`.
 ///
 /// # Safety
 ///
-/// `page` and `js_expr` must be valid pointers.
+/// All pointer arguments must be valid or NULL. `page_ranges`, if not NULL, must be a
+/// valid C string.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_wait_for_condition(
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn page_print_to_pdf(
     page: *mut Page,
-    js_expr: *const std::ffi::c_char,
-    timeout_secs: u64,
+    paper_width: f64,
+    paper_height: f64,
+    margin_top: f64,
+    margin_bottom: f64,
+    margin_left: f64,
+    margin_right: f64,
+    landscape: i32,
+    scale: f64,
+    background: i32,
+    page_ranges: *const std::ffi::c_char,
+    prefer_css_page_size: i32,
+    out_data: *mut *mut u8,
+    out_len: *mut usize,
 ) -> i32 {
-    if page.is_null() || js_expr.is_null() {
+    if page.is_null() || out_data.is_null() || out_len.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    let expr = match unsafe { std::ffi::CStr::from_ptr(js_expr) }.to_str() {
-        Ok(s) => s,
-        Err(_) => return PAGE_ERR_JS,
+    let page_ranges = if page_ranges.is_null() {
+        None
+    } else {
+        match unsafe { std::ffi::CStr::from_ptr(page_ranges) }.to_str() {
+            Ok(s) => parse_page_ranges(s),
+            Err(_) => return PAGE_ERR_JS,
+        }
     };
-    match page.wait_for_condition(expr, timeout_secs) {
-        Ok(()) => PAGE_OK,
+    let opts = PdfOptions {
+        paper_width,
+        paper_height,
+        margin_top,
+        margin_bottom,
+        margin_left,
+        margin_right,
+        orientation: if landscape != 0 {
+            Orientation::Landscape
+        } else {
+            Orientation::Portrait
+        },
+        scale,
+        background: background != 0,
+        page_ranges,
+        prefer_css_page_size: prefer_css_page_size != 0,
+    };
+    match page.print_to_pdf(opts) {
+        Ok(pdf_bytes) => {
+            let boxed = pdf_bytes.into_boxed_slice();
+            let len = boxed.len();
+            let ptr = Box::into_raw(boxed) as *mut u8;
+            unsafe {
+                *out_data = ptr;
+                *out_len = len;
+            }
+            PAGE_OK
+        }
         Err(e) => error_code(&e),
     }
 }
 
-/// Wait for a fixed number of seconds.
+/// Serialize the page into a fully self-contained HTML document with every
+/// subresource (images, stylesheets, scripts, fonts) inlined as `data:` URIs.
+///
+/// `flags` is a bitmask of `archive_flags` values from [`crate::types`].
+///
+/// On success, `*out_html` and `*out_len` are set. Free with `page_string_free()`.
 ///
 /// # Safety
 ///
-/// `page` must be a valid pointer.
+/// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_wait(page: *mut Page, seconds: f64) -> i32 {
-    if page.is_null() {
+pub unsafe extern "C" fn page_save_archive(
+    page: *mut Page,
+    flags: u32,
+    out_html: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || out_html.is_null() || out_len.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    page.wait(seconds);
-    PAGE_OK
+    match page.save_archive(flags) {
+        Ok(html) => match std::ffi::CString::new(html) {
+            Ok(cstr) => {
+                let len = cstr.as_bytes().len();
+                let ptr = cstr.into_raw();
+                unsafe {
+                    *out_html = ptr;
+                    *out_len = len;
+                }
+                PAGE_OK
+            }
+            Err(_) => PAGE_ERR_JS,
+        },
+        Err(e) => error_code(&e),
+    }
 }
 
-/// Wait for the next navigation to complete.
+/// Alias for `page_save_archive()`: serialize the active page into one self-contained
+/// HTML "monolith" document with every external resource embedded as a `data:` URI, so
+/// the caller can archive a scraped page without any follow-up network fetches.
 ///
 /// # Safety
 ///
-/// `page` must be a valid pointer.
+/// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_wait_for_navigation(page: *mut Page, timeout_secs: u64) -> i32 {
-    if page.is_null() {
-        return PAGE_ERR_NULL_PTR;
-    }
-    let page = unsafe { &*page };
-    match page.wait_for_navigation(timeout_secs) {
-        Ok(()) => PAGE_OK,
-        Err(e) => error_code(&e),
-    }
+pub unsafe extern "C" fn page_save_monolith(
+    page: *mut Page,
+    flags: u32,
+    out_html: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    unsafe { page_save_archive(page, flags, out_html, out_len) }
 }
 
-/// Wait until no new network requests arrive for `idle_ms` milliseconds.
+/// Capture the page HTML.
+///
+/// On success, `*out_html` and `*out_len` are set. Free with `page_string_free()`.
 ///
 /// # Safety
 ///
-/// `page` must be a valid pointer.
+/// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_wait_for_network_idle(
+pub unsafe extern "C" fn page_html(
     page: *mut Page,
-    idle_ms: u64,
-    timeout_secs: u64,
+    out_html: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
 ) -> i32 {
-    if page.is_null() {
+    if page.is_null() || out_html.is_null() || out_len.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    match page.wait_for_network_idle(idle_ms, timeout_secs) {
-        Ok(()) => PAGE_OK,
+    match page.html() {
+        Ok(html) => match std::ffi::CString::new(html) {
+            Ok(cstr) => {
+                let len = cstr.as_bytes().len();
+                let ptr = cstr.into_raw();
+                unsafe {
+                    *out_html = ptr;
+                    *out_len = len;
+                }
+                PAGE_OK
+            }
+            Err(_) => PAGE_ERR_JS,
+        },
         Err(e) => error_code(&e),
     }
 }
 
-// -- Input FFI --
-
-/// Click at the given coordinates.
+/// Capture the page HTML as it would render with JavaScript disabled: `<noscript>`
+/// content is expanded in place and all scripts/event-handler attributes are
+/// stripped. See [`crate::engine::PageEngine::html_static`].
+///
+/// On success, `*out_html` and `*out_len` are set. Free with `page_string_free()`.
 ///
 /// # Safety
 ///
-/// `page` must be a valid pointer.
+/// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_click(page: *mut Page, x: f32, y: f32) -> i32 {
-    if page.is_null() {
+pub unsafe extern "C" fn page_html_static(
+    page: *mut Page,
+    out_html: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || out_html.is_null() || out_len.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    match page.click(x, y) {
-        Ok(()) => PAGE_OK,
+    match page.html_static() {
+        Ok(html) => match std::ffi::CString::new(html) {
+            Ok(cstr) => {
+                let len = cstr.as_bytes().len();
+                let ptr = cstr.into_raw();
+                unsafe {
+                    *out_html = ptr;
+                    *out_len = len;
+                }
+                PAGE_OK
+            }
+            Err(_) => PAGE_ERR_JS,
+        },
         Err(e) => error_code(&e),
     }
 }
 
-/// Click on an element matching a CSS selector.
+// -- Page info --
+
+/// Get the current page URL.
 ///
 /// # Safety
 ///
-/// `page` and `selector` must be valid pointers.
+/// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_click_selector(
+pub unsafe extern "C" fn page_url(
     page: *mut Page,
-    selector: *const std::ffi::c_char,
+    out_url: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
 ) -> i32 {
-    if page.is_null() || selector.is_null() {
+    if page.is_null() || out_url.is_null() || out_len.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
-        Ok(s) => s,
-        Err(_) => return PAGE_ERR_JS,
-    };
-    match page.click_selector(sel) {
-        Ok(()) => PAGE_OK,
-        Err(e) => error_code(&e),
+    match page.url() {
+        Some(url_str) => match std::ffi::CString::new(url_str) {
+            Ok(cstr) => {
+                let len = cstr.as_bytes().len();
+                let ptr = cstr.into_raw();
+                unsafe {
+                    *out_url = ptr;
+                    *out_len = len;
+                }
+                PAGE_OK
+            }
+            Err(_) => PAGE_ERR_JS,
+        },
+        None => PAGE_ERR_NO_PAGE,
     }
 }
 
-/// Type text by sending individual key events.
+/// Get the current page title.
 ///
 /// # Safety
 ///
-/// `page` and `text` must be valid pointers.
+/// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_type_text(page: *mut Page, text: *const std::ffi::c_char) -> i32 {
-    if page.is_null() || text.is_null() {
+pub unsafe extern "C" fn page_title(
+    page: *mut Page,
+    out_title: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || out_title.is_null() || out_len.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    let text_str = match unsafe { std::ffi::CStr::from_ptr(text) }.to_str() {
-        Ok(s) => s,
-        Err(_) => return PAGE_ERR_JS,
-    };
-    match page.type_text(text_str) {
-        Ok(()) => PAGE_OK,
-        Err(e) => error_code(&e),
+    match page.title() {
+        Some(title_str) => match std::ffi::CString::new(title_str) {
+            Ok(cstr) => {
+                let len = cstr.as_bytes().len();
+                let ptr = cstr.into_raw();
+                unsafe {
+                    *out_title = ptr;
+                    *out_len = len;
+                }
+                PAGE_OK
+            }
+            Err(_) => PAGE_ERR_JS,
+        },
+        None => PAGE_ERR_NO_PAGE,
     }
 }
 
-/// Press a single key by name (e.g. "Enter", "Tab", "a").
+/// Gather Open Graph/Twitter Card/canonical-link/`ld+json` metadata for the active
+/// page as a JSON-encoded `PageMetadata`. See `Page::metadata`. Free the returned
+/// string with `page_string_free()`.
 ///
 /// # Safety
 ///
-/// `page` and `key_name` must be valid pointers.
+/// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_key_press(page: *mut Page, key_name: *const std::ffi::c_char) -> i32 {
-    if page.is_null() || key_name.is_null() {
+pub unsafe extern "C" fn page_metadata(
+    page: *mut Page,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || out_json.is_null() || out_len.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    let name = match unsafe { std::ffi::CStr::from_ptr(key_name) }.to_str() {
-        Ok(s) => s,
-        Err(_) => return PAGE_ERR_JS,
-    };
-    match page.key_press(name) {
-        Ok(()) => PAGE_OK,
+    match page.metadata() {
+        Ok(metadata) => {
+            let json = serde_json::to_string(&metadata).unwrap_or_else(|_| "null".to_string());
+            match std::ffi::CString::new(json) {
+                Ok(cstr) => {
+                    let len = cstr.as_bytes().len();
+                    let ptr = cstr.into_raw();
+                    unsafe {
+                        *out_json = ptr;
+                        *out_len = len;
+                    }
+                    PAGE_OK
+                }
+                Err(_) => PAGE_ERR_JS,
+            }
+        }
         Err(e) => error_code(&e),
     }
 }
 
-/// Move the mouse to the given coordinates.
+/// Run a readability-style extraction over the loaded page, writing the
+/// resulting JSON-encoded `Article` (`title`, `byline`, `content_html`, `text`,
+/// `lang`) to `*out_json`. Free with `page_string_free()`.
 ///
 /// # Safety
 ///
-/// `page` must be a valid pointer.
+/// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_mouse_move(page: *mut Page, x: f32, y: f32) -> i32 {
-    if page.is_null() {
+pub unsafe extern "C" fn page_extract_article(
+    page: *mut Page,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || out_json.is_null() || out_len.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    match page.mouse_move(x, y) {
-        Ok(()) => PAGE_OK,
+    match page.extract_article() {
+        Ok(article) => {
+            let json = serde_json::to_string(&article).unwrap_or_else(|_| "null".to_string());
+            match std::ffi::CString::new(json) {
+                Ok(cstr) => {
+                    let len = cstr.as_bytes().len();
+                    let ptr = cstr.into_raw();
+                    unsafe {
+                        *out_json = ptr;
+                        *out_len = len;
+                    }
+                    PAGE_OK
+                }
+                Err(_) => PAGE_ERR_JS,
+            }
+        }
         Err(e) => error_code(&e),
     }
 }
 
-// -- Scroll FFI --
-
-/// Scroll the viewport by the given pixel deltas.
+/// Extract the page's main article content and package it, with its images,
+/// as a single EPUB file at `dest_path`.
 ///
 /// # Safety
 ///
-/// `page` must be a valid pointer.
+/// All pointer arguments must be valid or NULL. `dest_path` must be a valid
+/// NUL-terminated UTF-8 C string.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_scroll(page: *mut Page, delta_x: f64, delta_y: f64) -> i32 {
-    if page.is_null() {
+pub unsafe extern "C" fn page_save_epub(page: *mut Page, dest_path: *const std::ffi::c_char) -> i32 {
+    if page.is_null() || dest_path.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    match page.scroll(delta_x, delta_y) {
+    let path_str = match unsafe { std::ffi::CStr::from_ptr(dest_path) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.save_epub(path_str) {
         Ok(()) => PAGE_OK,
         Err(e) => error_code(&e),
     }
 }
 
-/// Scroll an element matching a CSS selector into view.
+/// Render the page (or the subtree rooted at `selector`, if non-NULL) as
+/// Markdown. See [`crate::engine::PageEngine::markdown`]. Free the returned
+/// string with `page_string_free()`.
 ///
 /// # Safety
 ///
-/// `page` and `selector` must be valid pointers.
+/// All pointer arguments must be valid or NULL. `selector`, if non-NULL, must
+/// be a valid NUL-terminated UTF-8 C string.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_scroll_to_selector(
+pub unsafe extern "C" fn page_markdown(
     page: *mut Page,
     selector: *const std::ffi::c_char,
+    out_markdown: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
 ) -> i32 {
-    if page.is_null() || selector.is_null() {
+    if page.is_null() || out_markdown.is_null() || out_len.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
-        Ok(s) => s,
-        Err(_) => return PAGE_ERR_JS,
+    let selector_str = if selector.is_null() {
+        None
+    } else {
+        match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
+            Ok(s) => Some(s),
+            Err(_) => return PAGE_ERR_JS,
+        }
     };
-    match page.scroll_to_selector(sel) {
-        Ok(()) => PAGE_OK,
+    match page.markdown(selector_str) {
+        Ok(md) => match std::ffi::CString::new(md) {
+            Ok(cstr) => {
+                let len = cstr.as_bytes().len();
+                let ptr = cstr.into_raw();
+                unsafe {
+                    *out_markdown = ptr;
+                    *out_len = len;
+                }
+                PAGE_OK
+            }
+            Err(_) => PAGE_ERR_JS,
+        },
         Err(e) => error_code(&e),
     }
 }
 
-// -- Select FFI --
+// -- Events (JSON) --
 
-/// Select an option in a `<select>` element by value.
+/// Get console messages as a JSON array.
 ///
 /// # Safety
 ///
-/// `page`, `selector`, and `value` must be valid pointers.
+/// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_select_option(
+pub unsafe extern "C" fn page_console_messages(
     page: *mut Page,
-    selector: *const std::ffi::c_char,
-    value: *const std::ffi::c_char,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
 ) -> i32 {
-    if page.is_null() || selector.is_null() || value.is_null() {
+    if page.is_null() || out_json.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let msgs = page.console_messages();
+    let json = serde_json::to_string(&msgs).unwrap_or_else(|_| "[]".to_string());
+    match std::ffi::CString::new(json) {
+        Ok(cstr) => {
+            let len = cstr.as_bytes().len();
+            let ptr = cstr.into_raw();
+            unsafe {
+                *out_json = ptr;
+                *out_len = len;
+            }
+            PAGE_OK
+        }
+        Err(_) => PAGE_ERR_JS,
+    }
+}
+
+/// Begin JS coverage collection. See `PageEngine::start_js_coverage`.
+///
+/// # Safety
+///
+/// `page` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_start_js_coverage(page: *mut Page) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.start_js_coverage() {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Stop JS coverage collection and return a JSON array of `CoverageEntry`. See
+/// `PageEngine::stop_js_coverage` for what granularity this can actually report.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_stop_js_coverage(
+    page: *mut Page,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || out_json.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.stop_js_coverage() {
+        Ok(entries) => {
+            let json = serde_json::to_string(&entries).unwrap_or_else(|_| "[]".to_string());
+            match std::ffi::CString::new(json) {
+                Ok(cstr) => {
+                    let len = cstr.as_bytes().len();
+                    let ptr = cstr.into_raw();
+                    unsafe {
+                        *out_json = ptr;
+                        *out_len = len;
+                    }
+                    PAGE_OK
+                }
+                Err(_) => PAGE_ERR_JS,
+            }
+        }
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Begin CSS coverage collection. See `PageEngine::start_css_coverage`.
+///
+/// # Safety
+///
+/// `page` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_start_css_coverage(page: *mut Page) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.start_css_coverage() {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Stop CSS coverage collection and return a JSON array of `CoverageEntry`. See
+/// `PageEngine::stop_css_coverage` for the rule-level granularity this provides.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_stop_css_coverage(
+    page: *mut Page,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || out_json.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.stop_css_coverage() {
+        Ok(entries) => {
+            let json = serde_json::to_string(&entries).unwrap_or_else(|_| "[]".to_string());
+            match std::ffi::CString::new(json) {
+                Ok(cstr) => {
+                    let len = cstr.as_bytes().len();
+                    let ptr = cstr.into_raw();
+                    unsafe {
+                        *out_json = ptr;
+                        *out_len = len;
+                    }
+                    PAGE_OK
+                }
+                Err(_) => PAGE_ERR_JS,
+            }
+        }
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Get captured JS dialogs (`alert`/`confirm`/`prompt`) as a JSON array.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_dialog_messages(
+    page: *mut Page,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || out_json.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let msgs = page.dialog_messages();
+    let json = serde_json::to_string(&msgs).unwrap_or_else(|_| "[]".to_string());
+    match std::ffi::CString::new(json) {
+        Ok(cstr) => {
+            let len = cstr.as_bytes().len();
+            let ptr = cstr.into_raw();
+            unsafe {
+                *out_json = ptr;
+                *out_len = len;
+            }
+            PAGE_OK
+        }
+        Err(_) => PAGE_ERR_JS,
+    }
+}
+
+/// Get network requests as a JSON array.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_network_requests(
+    page: *mut Page,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || out_json.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let reqs = page.network_requests();
+    let json = serde_json::to_string(&reqs).unwrap_or_else(|_| "[]".to_string());
+    match std::ffi::CString::new(json) {
+        Ok(cstr) => {
+            let len = cstr.as_bytes().len();
+            let ptr = cstr.into_raw();
+            unsafe {
+                *out_json = ptr;
+                *out_len = len;
+            }
+            PAGE_OK
+        }
+        Err(_) => PAGE_ERR_JS,
+    }
+}
+
+/// Drain network requests that have response data recorded (status/headers/body),
+/// as a JSON array. See `Page::network_responses`.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_network_responses(
+    page: *mut Page,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || out_json.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let reqs = page.network_responses();
+    let json = serde_json::to_string(&reqs).unwrap_or_else(|_| "[]".to_string());
+    match std::ffi::CString::new(json) {
+        Ok(cstr) => {
+            let len = cstr.as_bytes().len();
+            let ptr = cstr.into_raw();
+            unsafe {
+                *out_json = ptr;
+                *out_len = len;
+            }
+            PAGE_OK
+        }
+        Err(_) => PAGE_ERR_JS,
+    }
+}
+
+/// Look up the captured response body for the most recent request to `url`. See
+/// `Page::get_response_body`. Free the returned buffer with `page_buffer_free()`.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL; `url` must be a valid C string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_get_response_body(
+    page: *mut Page,
+    url: *const std::ffi::c_char,
+    out_data: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || url.is_null() || out_data.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let url_str = match unsafe { std::ffi::CStr::from_ptr(url) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.get_response_body(url_str) {
+        Some(body) => {
+            let boxed = body.into_boxed_slice();
+            let len = boxed.len();
+            let ptr = Box::into_raw(boxed) as *mut u8;
+            unsafe {
+                *out_data = ptr;
+                *out_len = len;
+            }
+            PAGE_OK
+        }
+        None => PAGE_ERR_NO_PAGE,
+    }
+}
+
+/// Look up the captured response body for a specific request by id (see the
+/// `request_id` field of the JSON from `page_network_requests()`/`page_network_responses()`),
+/// returned as a JSON-encoded `ResponseBody` (`content_type`, `was_truncated`,
+/// base64 `data_base64`). See `Page::response_body`. Free the returned string with
+/// `page_string_free()`.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL; `request_id` must be a valid C string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_response_body(
+    page: *mut Page,
+    request_id: *const std::ffi::c_char,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || request_id.is_null() || out_json.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let request_id_str = match unsafe { std::ffi::CStr::from_ptr(request_id) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.response_body(request_id_str) {
+        Ok(body) => {
+            let json = serde_json::to_string(&body).unwrap_or_else(|_| "null".to_string());
+            match std::ffi::CString::new(json) {
+                Ok(cstr) => {
+                    let len = cstr.as_bytes().len();
+                    let ptr = cstr.into_raw();
+                    unsafe {
+                        *out_json = ptr;
+                        *out_len = len;
+                    }
+                    PAGE_OK
+                }
+                Err(_) => PAGE_ERR_JS,
+            }
+        }
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Drain the audit log of intercepted requests (block/redirect/fulfill decisions from
+/// `page_on_request`-style callbacks or `page_add_route` rules) as a JSON array.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_intercepted_requests(
+    page: *mut Page,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || out_json.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let reqs = page.intercepted_requests();
+    let json = serde_json::to_string(&reqs).unwrap_or_else(|_| "[]".to_string());
+    match std::ffi::CString::new(json) {
+        Ok(cstr) => {
+            let len = cstr.as_bytes().len();
+            let ptr = cstr.into_raw();
+            unsafe {
+                *out_json = ptr;
+                *out_len = len;
+            }
+            PAGE_OK
+        }
+        Err(_) => PAGE_ERR_JS,
+    }
+}
+
+/// Drain captured network requests and get them as a HAR 1.2 log (JSON).
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_har(
+    page: *mut Page,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || out_json.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.har() {
+        Ok(json) => match std::ffi::CString::new(json) {
+            Ok(cstr) => {
+                let len = cstr.as_bytes().len();
+                let ptr = cstr.into_raw();
+                unsafe {
+                    *out_json = ptr;
+                    *out_len = len;
+                }
+                PAGE_OK
+            }
+            Err(_) => PAGE_ERR_JS,
+        },
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Alias for `page_har()`: drain captured network requests and get them as a HAR 1.2
+/// log (JSON), loadable directly in browser devtools, Charles, and other HAR viewers.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_network_har(
+    page: *mut Page,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    unsafe { page_har(page, out_json, out_len) }
+}
+
+// -- Wait FFI --
+
+/// Wait for a CSS selector to match an element.
+///
+/// # Safety
+///
+/// `page` and `selector` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_wait_for_selector(
+    page: *mut Page,
+    selector: *const std::ffi::c_char,
+    timeout_secs: u64,
+) -> i32 {
+    if page.is_null() || selector.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.wait_for_selector(sel, timeout_secs) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Wait for a CSS selector to match no element on the page.
+///
+/// # Safety
+///
+/// `page` and `selector` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_wait_for_selector_gone(
+    page: *mut Page,
+    selector: *const std::ffi::c_char,
+    timeout_secs: u64,
+) -> i32 {
+    if page.is_null() || selector.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.wait_for_selector_gone(sel, timeout_secs) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Wait for a JS expression to evaluate to a truthy value.
+///
+/// # Safety
+///
+/// `page` and `js_expr` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_wait_for_condition(
+    page: *mut Page,
+    js_expr: *const std::ffi::c_char,
+    timeout_secs: u64,
+) -> i32 {
+    if page.is_null() || js_expr.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let expr = match unsafe { std::ffi::CStr::from_ptr(js_expr) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.wait_for_condition(expr, timeout_secs) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Wait for a fixed number of seconds.
+///
+/// # Safety
+///
+/// `page` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_wait(page: *mut Page, seconds: f64) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    page.wait(seconds);
+    PAGE_OK
+}
+
+/// Wait for the next navigation to complete.
+///
+/// # Safety
+///
+/// `page` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_wait_for_navigation(page: *mut Page, timeout_secs: u64) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.wait_for_navigation(timeout_secs) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Wait until no new network requests arrive for `idle_ms` milliseconds.
+///
+/// # Safety
+///
+/// `page` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_wait_for_network_idle(
+    page: *mut Page,
+    idle_ms: u64,
+    timeout_secs: u64,
+) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.wait_for_network_idle(idle_ms, timeout_secs) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+// -- Input FFI --
+
+/// Click at the given coordinates.
+///
+/// # Safety
+///
+/// `page` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_click(page: *mut Page, x: f32, y: f32) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.click(x, y) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Click on an element matching a CSS selector.
+///
+/// # Safety
+///
+/// `page` and `selector` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_click_selector(
+    page: *mut Page,
+    selector: *const std::ffi::c_char,
+) -> i32 {
+    if page.is_null() || selector.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.click_selector(sel) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Focus an element matching a CSS selector.
+///
+/// # Safety
+///
+/// `page` and `selector` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_focus(page: *mut Page, selector: *const std::ffi::c_char) -> i32 {
+    if page.is_null() || selector.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.focus(sel) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Type text by sending individual key events.
+///
+/// # Safety
+///
+/// `page` and `text` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_type_text(page: *mut Page, text: *const std::ffi::c_char) -> i32 {
+    if page.is_null() || text.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let text_str = match unsafe { std::ffi::CStr::from_ptr(text) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.type_text(text_str) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Press a single key by name (e.g. "Enter", "Tab", "a").
+///
+/// # Safety
+///
+/// `page` and `key_name` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_key_press(page: *mut Page, key_name: *const std::ffi::c_char) -> i32 {
+    if page.is_null() || key_name.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let name = match unsafe { std::ffi::CStr::from_ptr(key_name) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.key_press(name) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Move the mouse to the given coordinates.
+///
+/// # Safety
+///
+/// `page` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_mouse_move(page: *mut Page, x: f32, y: f32) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.mouse_move(x, y) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Execute a W3C WebDriver-style batched Actions payload: a JSON array of input
+/// sources (`"pointer"`, `"key"`, `"wheel"`, or `"none"`), each with an ordered `actions` list.
+/// See `PageEngine::perform_actions` for the full action vocabulary.
+///
+/// # Safety
+///
+/// `page` and `actions_json` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_perform_actions(
+    page: *mut Page,
+    actions_json: *const std::ffi::c_char,
+) -> i32 {
+    if page.is_null() || actions_json.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let json = match unsafe { std::ffi::CStr::from_ptr(actions_json) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.perform_actions(json) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+// -- Scroll FFI --
+
+/// Scroll the viewport by the given pixel deltas.
+///
+/// # Safety
+///
+/// `page` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_scroll(page: *mut Page, delta_x: f64, delta_y: f64) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.scroll(delta_x, delta_y) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Scroll an element matching a CSS selector into view.
+///
+/// # Safety
+///
+/// `page` and `selector` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_scroll_to_selector(
+    page: *mut Page,
+    selector: *const std::ffi::c_char,
+) -> i32 {
+    if page.is_null() || selector.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.scroll_to_selector(sel) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+// -- Select FFI --
+
+/// Select an option in a `<select>` element by value.
+///
+/// # Safety
+///
+/// `page`, `selector`, and `value` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_select_option(
+    page: *mut Page,
+    selector: *const std::ffi::c_char,
+    value: *const std::ffi::c_char,
+) -> i32 {
+    if page.is_null() || selector.is_null() || value.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    let val = match unsafe { std::ffi::CStr::from_ptr(value) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.select_option(sel, val) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+// -- File upload FFI --
+
+/// Set files on an `<input type="file">` element.
+///
+/// `paths` is a comma-separated list of file paths. Each file is read from disk,
+/// its MIME type inferred from the extension, and injected via the DataTransfer API.
+///
+/// # Safety
+///
+/// `page`, `selector`, and `paths` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_set_input_files(
+    page: *mut Page,
+    selector: *const std::ffi::c_char,
+    paths: *const std::ffi::c_char,
+) -> i32 {
+    if page.is_null() || selector.is_null() || paths.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    let paths_str = match unsafe { std::ffi::CStr::from_ptr(paths) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+
+    let mut files = Vec::new();
+    for path_str in paths_str
+        .split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+    {
+        let path = std::path::Path::new(path_str);
+        let data = match std::fs::read(path) {
+            Ok(d) => d,
+            Err(_) => return PAGE_ERR_JS,
+        };
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("file")
+            .to_string();
+        let mime_type = match path.extension().and_then(|e| e.to_str()) {
+            Some("txt") => "text/plain",
+            Some("html") | Some("htm") => "text/html",
+            Some("css") => "text/css",
+            Some("js") => "application/javascript",
+            Some("json") => "application/json",
+            Some("xml") => "application/xml",
+            Some("pdf") => "application/pdf",
+            Some("png") => "image/png",
+            Some("jpg") | Some("jpeg") => "image/jpeg",
+            Some("gif") => "image/gif",
+            Some("svg") => "image/svg+xml",
+            Some("webp") => "image/webp",
+            Some("zip") => "application/zip",
+            Some("csv") => "text/csv",
+            _ => "application/octet-stream",
+        }
+        .to_string();
+        files.push(InputFile {
+            name,
+            mime_type,
+            data,
+        });
+    }
+
+    match page.set_input_files(sel, files) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+// -- Cookies FFI --
+
+/// Get cookies for the current page.
+///
+/// On success, `*out_cookies` and `*out_len` are set. Free with `page_string_free()`.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_get_cookies(
+    page: *mut Page,
+    out_cookies: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || out_cookies.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.get_cookies() {
+        Ok(cookies) => match std::ffi::CString::new(cookies) {
+            Ok(cstr) => {
+                let len = cstr.as_bytes().len();
+                let ptr = cstr.into_raw();
+                unsafe {
+                    *out_cookies = ptr;
+                    *out_len = len;
+                }
+                PAGE_OK
+            }
+            Err(_) => PAGE_ERR_JS,
+        },
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Set a cookie via `document.cookie`.
+///
+/// # Safety
+///
+/// `page` and `cookie` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_set_cookie(page: *mut Page, cookie: *const std::ffi::c_char) -> i32 {
+    if page.is_null() || cookie.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let cookie_str = match unsafe { std::ffi::CStr::from_ptr(cookie) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.set_cookie_raw(cookie_str) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Clear all cookies for the current page.
+///
+/// # Safety
+///
+/// `page` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_clear_cookies(page: *mut Page) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.clear_cookies() {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Get all cookies as a JSON array of structured `{name, value, domain, path, expires,
+/// http_only, secure, same_site}` objects, backed by the page's cookie jar.
+///
+/// On success, `*out_json` and `*out_len` are set. Free with `page_string_free()`.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_get_cookies_json(
+    page: *mut Page,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || out_json.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.get_cookies_json() {
+        Ok(json) => match std::ffi::CString::new(json) {
+            Ok(cstr) => {
+                let len = cstr.as_bytes().len();
+                let ptr = cstr.into_raw();
+                unsafe {
+                    *out_json = ptr;
+                    *out_len = len;
+                }
+                PAGE_OK
+            }
+            Err(_) => PAGE_ERR_JS,
+        },
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Set one structured cookie from a JSON object with `name`, `value`, and optional
+/// `domain`, `path`, `expires` (ms since epoch), `secure`, and `same_site` fields.
+///
+/// # Safety
+///
+/// `page` and `cookie_json` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_set_cookie_struct(
+    page: *mut Page,
+    cookie_json: *const std::ffi::c_char,
+) -> i32 {
+    if page.is_null() || cookie_json.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let json = match unsafe { std::ffi::CStr::from_ptr(cookie_json) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.set_cookie_struct(json) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Delete a single cookie by name. `domain` and `path` may be NULL.
+///
+/// # Safety
+///
+/// `page` and `name` must be valid pointers. `domain` and `path` may be NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_delete_cookie(
+    page: *mut Page,
+    name: *const std::ffi::c_char,
+    domain: *const std::ffi::c_char,
+    path: *const std::ffi::c_char,
+) -> i32 {
+    if page.is_null() || name.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let name_str = match unsafe { std::ffi::CStr::from_ptr(name) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    let domain_str = if domain.is_null() {
+        None
+    } else {
+        match unsafe { std::ffi::CStr::from_ptr(domain) }.to_str() {
+            Ok(s) => Some(s),
+            Err(_) => return PAGE_ERR_JS,
+        }
+    };
+    let path_str = if path.is_null() {
+        None
+    } else {
+        match unsafe { std::ffi::CStr::from_ptr(path) }.to_str() {
+            Ok(s) => Some(s),
+            Err(_) => return PAGE_ERR_JS,
+        }
+    };
+    match page.delete_cookie(name_str, domain_str, path_str) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Get all cookies visible to the page as a JSON array of typed cookie objects (same
+/// shape and caveats as `page_get_cookies_json`).
+///
+/// On success, `*out_json` and `*out_len` are set. Free with `page_string_free()`.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_cookies(
+    page: *mut Page,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || out_json.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let cookies = match page.cookies() {
+        Ok(cookies) => cookies,
+        Err(e) => return error_code(&e),
+    };
+    let json = match serde_json::to_string(&cookies) {
+        Ok(json) => json,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match std::ffi::CString::new(json) {
+        Ok(cstr) => {
+            let len = cstr.as_bytes().len();
+            let ptr = cstr.into_raw();
+            unsafe {
+                *out_json = ptr;
+                *out_len = len;
+            }
+            PAGE_OK
+        }
+        Err(_) => PAGE_ERR_JS,
+    }
+}
+
+/// Set multiple cookies from a JSON array of cookie objects (same shape as
+/// `page_set_cookie_struct`, but batched). Unlike `page_set_cookie_struct`, rejects
+/// any cookie in the array with `http_only` set, since those can't be created from
+/// script.
+///
+/// # Safety
+///
+/// `page` and `cookies_json` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_set_cookies_json(
+    page: *mut Page,
+    cookies_json: *const std::ffi::c_char,
+) -> i32 {
+    if page.is_null() || cookies_json.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let json = match unsafe { std::ffi::CStr::from_ptr(cookies_json) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    let cookies: Vec<crate::types::Cookie> = match serde_json::from_str(json) {
+        Ok(cookies) => cookies,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.set_cookies(&cookies) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+// -- Init scripts FFI --
+
+/// Register a script to run on every document, akin to CDP's
+/// `Page.addScriptToEvaluateOnNewDocument`. See `PageEngine::add_init_script` for
+/// how early it actually runs.
+///
+/// # Safety
+///
+/// `page` and `script` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_add_init_script(
+    page: *mut Page,
+    script: *const std::ffi::c_char,
+    out_script_id: *mut u32,
+) -> i32 {
+    if page.is_null() || script.is_null() || out_script_id.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let script_str = match unsafe { std::ffi::CStr::from_ptr(script) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    let id = page.add_init_script(script_str);
+    unsafe {
+        *out_script_id = id;
+    }
+    PAGE_OK
+}
+
+/// Remove a previously registered init script by the id returned from
+/// `page_add_init_script()`. Returns `PAGE_OK` whether or not a script with that id
+/// was still registered; check `*out_removed` to tell the two cases apart.
+///
+/// # Safety
+///
+/// `page` and `out_removed` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_remove_init_script(
+    page: *mut Page,
+    script_id: u32,
+    out_removed: *mut i32,
+) -> i32 {
+    if page.is_null() || out_removed.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let removed = page.remove_init_script(script_id);
+    unsafe {
+        *out_removed = removed as i32;
+    }
+    PAGE_OK
+}
+
+/// Override `navigator.userAgent` for subsequent page loads. See
+/// `PageEngine::set_user_agent` for why this can't change the real network-level
+/// User-Agent header once the engine is already running.
+///
+/// # Safety
+///
+/// `page` and `user_agent` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_set_user_agent(
+    page: *mut Page,
+    user_agent: *const std::ffi::c_char,
+) -> i32 {
+    if page.is_null() || user_agent.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let ua_str = match unsafe { std::ffi::CStr::from_ptr(user_agent) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    page.set_user_agent(ua_str);
+    PAGE_OK
+}
+
+/// Replace the extra HTTP headers added to script-driven requests, from a JSON object
+/// of string keys to string values, e.g. `{"Accept-Language": "fr-FR"}`. See
+/// `PageEngine::set_extra_http_headers`.
+///
+/// # Safety
+///
+/// `page` and `headers_json` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_set_extra_http_headers(
+    page: *mut Page,
+    headers_json: *const std::ffi::c_char,
+) -> i32 {
+    if page.is_null() || headers_json.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let json = match unsafe { std::ffi::CStr::from_ptr(headers_json) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    let headers: std::collections::HashMap<String, String> = match serde_json::from_str(json) {
+        Ok(headers) => headers,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    page.set_extra_http_headers(headers);
+    PAGE_OK
+}
+
+/// Set (or replace) the HTTP Basic Auth credentials used for subsequent navigations.
+/// See `PageEngine::set_http_auth`.
+///
+/// # Safety
+///
+/// `page`, `username`, and `password` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_set_http_auth(
+    page: *mut Page,
+    username: *const std::ffi::c_char,
+    password: *const std::ffi::c_char,
+) -> i32 {
+    if page.is_null() || username.is_null() || password.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let username_str = match unsafe { std::ffi::CStr::from_ptr(username) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    let password_str = match unsafe { std::ffi::CStr::from_ptr(password) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    page.set_http_auth(username_str, password_str);
+    PAGE_OK
+}
+
+/// Override a `navigator` property for subsequent page loads. `field_name` must be one
+/// of `"userAgent"`, `"appVersion"`, `"platform"`, or `"language"`. See
+/// `PageEngine::set_navigator_override`.
+///
+/// # Safety
+///
+/// `page`, `field_name`, and `value` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_set_navigator_override(
+    page: *mut Page,
+    field_name: *const std::ffi::c_char,
+    value: *const std::ffi::c_char,
+) -> i32 {
+    if page.is_null() || field_name.is_null() || value.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let field_str = match unsafe { std::ffi::CStr::from_ptr(field_name) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    let value_str = match unsafe { std::ffi::CStr::from_ptr(value) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.set_navigator_override(field_str, value_str) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Override the `window.screen`/`devicePixelRatio` values JavaScript observes for
+/// subsequent page loads. See `PageEngine::set_viewport`.
+///
+/// # Safety
+///
+/// `page` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_set_viewport(
+    page: *mut Page,
+    width: u32,
+    height: u32,
+    device_scale: f32,
+) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    page.set_viewport(width, height, device_scale);
+    PAGE_OK
+}
+
+/// Apply full device emulation to the active page: unlike `page_set_viewport()`, this
+/// also resizes the rendering surface to `width*dpr x height*dpr` physical pixels. See
+/// `PageEngine::set_emulation`.
+///
+/// # Safety
+///
+/// `page` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_set_emulation(
+    page: *mut Page,
+    width: u32,
+    height: u32,
+    device_scale_factor: f32,
+    is_mobile: i32,
+    has_touch: i32,
+) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let settings = EmulationSettings {
+        width,
+        height,
+        device_scale_factor,
+        is_mobile: is_mobile != 0,
+        has_touch: has_touch != 0,
+    };
+    match page.set_emulation(settings) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Apply a device emulation preset in one call: viewport/touch, like
+/// `page_set_emulation()`, plus the device's user-agent string. See `PageEngine::emulate`.
+///
+/// # Safety
+///
+/// `page` and `user_agent` must be valid pointers; `user_agent` must be valid UTF-8.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_emulate(
+    page: *mut Page,
+    width: u32,
+    height: u32,
+    device_scale_factor: f32,
+    is_mobile: i32,
+    has_touch: i32,
+    user_agent: *const std::ffi::c_char,
+) -> i32 {
+    if page.is_null() || user_agent.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let ua_str = match unsafe { std::ffi::CStr::from_ptr(user_agent) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    let device = crate::types::DeviceDescriptor {
+        name: String::new(),
+        width,
+        height,
+        device_scale_factor,
+        is_mobile: is_mobile != 0,
+        has_touch: has_touch != 0,
+        user_agent: ua_str.to_string(),
+    };
+    match page.emulate(device) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Emulate `prefers-color-scheme`/print media for subsequent page loads.
+/// `media` is `"screen"`/`"print"` (or null to leave the media type unemulated);
+/// `features_json` is a JSON array of `[name, value]` pairs, e.g.
+/// `[["prefers-color-scheme", "dark"]]` (or null for no features). See
+/// `PageEngine::emulate_media`.
+///
+/// # Safety
+///
+/// `page` must be a valid pointer; `media`/`features_json`, if non-null, must be
+/// valid UTF-8.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_emulate_media(
+    page: *mut Page,
+    media: *const std::ffi::c_char,
+    features_json: *const std::ffi::c_char,
+) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let media_str = if media.is_null() {
+        None
+    } else {
+        match unsafe { std::ffi::CStr::from_ptr(media) }.to_str() {
+            Ok(s) => Some(s.to_string()),
+            Err(_) => return PAGE_ERR_JS,
+        }
+    };
+    let features = if features_json.is_null() {
+        Vec::new()
+    } else {
+        let json_str = match unsafe { std::ffi::CStr::from_ptr(features_json) }.to_str() {
+            Ok(s) => s,
+            Err(_) => return PAGE_ERR_JS,
+        };
+        match serde_json::from_str::<Vec<(String, String)>>(json_str) {
+            Ok(f) => f,
+            Err(_) => return PAGE_ERR_JS,
+        }
+    };
+    page.emulate_media(crate::types::MediaEmulation {
+        media: media_str,
+        features,
+    });
+    PAGE_OK
+}
+
+// -- Request interception FFI --
+
+/// Set URL patterns to block (comma-separated). Pass NULL to clear.
+///
+/// # Safety
+///
+/// `page` must be a valid pointer. `patterns` may be NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_block_urls(
+    page: *mut Page,
+    patterns: *const std::ffi::c_char,
+) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    if patterns.is_null() {
+        page.clear_blocked_urls();
+    } else {
+        let pat_str = match unsafe { std::ffi::CStr::from_ptr(patterns) }.to_str() {
+            Ok(s) => s,
+            Err(_) => return PAGE_ERR_JS,
+        };
+        let pats: Vec<String> = pat_str
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+        page.block_urls(pats);
+    }
+    PAGE_OK
+}
+
+/// Register a routing rule from a JSON object: `{pattern, resource_type?, method?,
+/// action, ...}` where `action` is `"block"`, `"redirect"` (with a `url` field), or
+/// `"fulfill"` (with `status`, `headers`, and a base64 `body`). `method`, if given,
+/// restricts the rule to one HTTP method (case-insensitive); omitted, it matches any
+/// method. Rules are evaluated in insertion order; the first match wins.
+///
+/// # Safety
+///
+/// `page` and `rule_json` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_add_route(
+    page: *mut Page,
+    rule_json: *const std::ffi::c_char,
+) -> i32 {
+    if page.is_null() || rule_json.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let json = match unsafe { std::ffi::CStr::from_ptr(rule_json) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.add_route(json) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Alias for `page_add_route()`.
+///
+/// # Safety
+///
+/// `page` and `rule_json` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_add_intercept_rule(
+    page: *mut Page,
+    rule_json: *const std::ffi::c_char,
+) -> i32 {
+    unsafe { page_add_route(page, rule_json) }
+}
+
+/// Remove all routing rules registered via `page_add_route()`.
+///
+/// # Safety
+///
+/// `page` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_clear_routes(page: *mut Page) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.clear_routes() {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+// -- Navigation FFI --
+
+/// Reload the current page.
+///
+/// # Safety
+///
+/// `page` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_reload(page: *mut Page) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.reload() {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Navigate back in history. Returns `PAGE_ERR_NO_PAGE` if no history.
+///
+/// # Safety
+///
+/// `page` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_go_back(page: *mut Page) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.go_back() {
+        Ok(true) => PAGE_OK,
+        Ok(false) => PAGE_ERR_NO_PAGE,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Navigate forward in history. Returns `PAGE_ERR_NO_PAGE` if no forward history.
+///
+/// # Safety
+///
+/// `page` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_go_forward(page: *mut Page) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.go_forward() {
+        Ok(true) => PAGE_OK,
+        Ok(false) => PAGE_ERR_NO_PAGE,
+        Err(e) => error_code(&e),
+    }
+}
+
+// -- Element info FFI --
+
+/// Get the bounding rectangle of an element as JSON (`{"x":..,"y":..,"width":..,"height":..}`).
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_element_rect(
+    page: *mut Page,
+    selector: *const std::ffi::c_char,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || selector.is_null() || out_json.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.element_rect(sel) {
+        Ok(rect) => {
+            let json = serde_json::to_string(&rect).unwrap_or_else(|_| "{}".to_string());
+            match std::ffi::CString::new(json) {
+                Ok(cstr) => {
+                    let len = cstr.as_bytes().len();
+                    let ptr = cstr.into_raw();
+                    unsafe {
+                        *out_json = ptr;
+                        *out_len = len;
+                    }
+                    PAGE_OK
+                }
+                Err(_) => PAGE_ERR_JS,
+            }
+        }
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Get the text content of an element.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_element_text(
+    page: *mut Page,
+    selector: *const std::ffi::c_char,
+    out_text: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || selector.is_null() || out_text.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.element_text(sel) {
+        Ok(text) => match std::ffi::CString::new(text) {
+            Ok(cstr) => {
+                let len = cstr.as_bytes().len();
+                let ptr = cstr.into_raw();
+                unsafe {
+                    *out_text = ptr;
+                    *out_len = len;
+                }
+                PAGE_OK
+            }
+            Err(_) => PAGE_ERR_JS,
+        },
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Get an attribute value of an element. Returns empty string if attribute doesn't exist.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_element_attribute(
+    page: *mut Page,
+    selector: *const std::ffi::c_char,
+    attribute: *const std::ffi::c_char,
+    out_value: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null()
+        || selector.is_null()
+        || attribute.is_null()
+        || out_value.is_null()
+        || out_len.is_null()
+    {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    let attr = match unsafe { std::ffi::CStr::from_ptr(attribute) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.element_attribute(sel, attr) {
+        Ok(value) => {
+            let s = value.unwrap_or_default();
+            match std::ffi::CString::new(s) {
+                Ok(cstr) => {
+                    let len = cstr.as_bytes().len();
+                    let ptr = cstr.into_raw();
+                    unsafe {
+                        *out_value = ptr;
+                        *out_len = len;
+                    }
+                    PAGE_OK
+                }
+                Err(_) => PAGE_ERR_JS,
+            }
+        }
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Get the outer HTML of an element.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_element_html(
+    page: *mut Page,
+    selector: *const std::ffi::c_char,
+    out_html: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || selector.is_null() || out_html.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.element_html(sel) {
+        Ok(html) => match std::ffi::CString::new(html) {
+            Ok(cstr) => {
+                let len = cstr.as_bytes().len();
+                let ptr = cstr.into_raw();
+                unsafe {
+                    *out_html = ptr;
+                    *out_len = len;
+                }
+                PAGE_OK
+            }
+            Err(_) => PAGE_ERR_JS,
+        },
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Parse a `Locator` out of a JSON-encoded `locator_json` C string
+/// (`{"css":"..."}` or `{"xpath":"..."}`), returning `PAGE_ERR_NULL_PTR` on a null
+/// pointer and `PAGE_ERR_JS` on invalid UTF-8 or malformed JSON.
+unsafe fn parse_locator_json(
+    locator_json: *const std::ffi::c_char,
+) -> Result<crate::types::Locator, i32> {
+    if locator_json.is_null() {
+        return Err(PAGE_ERR_NULL_PTR);
+    }
+    let json = unsafe { std::ffi::CStr::from_ptr(locator_json) }
+        .to_str()
+        .map_err(|_| PAGE_ERR_JS)?;
+    serde_json::from_str(json).map_err(|_| PAGE_ERR_JS)
+}
+
+/// Get the bounding rectangle of an element located by a `Locator` (CSS or XPath) as
+/// JSON (`{"x":..,"y":..,"width":..,"height":..}`). `locator_json` is
+/// `{"css":"..."}` or `{"xpath":"..."}`.
+///
+/// On success, `*out_json` and `*out_len` are set. Free with `page_string_free()`.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_element_rect_by(
+    page: *mut Page,
+    locator_json: *const std::ffi::c_char,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || out_json.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let locator = match unsafe { parse_locator_json(locator_json) } {
+        Ok(locator) => locator,
+        Err(code) => return code,
+    };
+    let page = unsafe { &*page };
+    match page.element_rect_by(locator) {
+        Ok(rect) => {
+            let json = serde_json::to_string(&rect).unwrap_or_else(|_| "{}".to_string());
+            match std::ffi::CString::new(json) {
+                Ok(cstr) => {
+                    let len = cstr.as_bytes().len();
+                    let ptr = cstr.into_raw();
+                    unsafe {
+                        *out_json = ptr;
+                        *out_len = len;
+                    }
+                    PAGE_OK
+                }
+                Err(_) => PAGE_ERR_JS,
+            }
+        }
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Get the text content of an element located by a `Locator` (CSS or XPath).
+/// `locator_json` is `{"css":"..."}` or `{"xpath":"..."}`.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_element_text_by(
+    page: *mut Page,
+    locator_json: *const std::ffi::c_char,
+    out_text: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || out_text.is_null() || out_len.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
-    let page = unsafe { &*page };
-    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
-        Ok(s) => s,
-        Err(_) => return PAGE_ERR_JS,
-    };
-    let val = match unsafe { std::ffi::CStr::from_ptr(value) }.to_str() {
-        Ok(s) => s,
-        Err(_) => return PAGE_ERR_JS,
+    let locator = match unsafe { parse_locator_json(locator_json) } {
+        Ok(locator) => locator,
+        Err(code) => return code,
     };
-    match page.select_option(sel, val) {
-        Ok(()) => PAGE_OK,
+    let page = unsafe { &*page };
+    match page.element_text_by(locator) {
+        Ok(text) => match std::ffi::CString::new(text) {
+            Ok(cstr) => {
+                let len = cstr.as_bytes().len();
+                let ptr = cstr.into_raw();
+                unsafe {
+                    *out_text = ptr;
+                    *out_len = len;
+                }
+                PAGE_OK
+            }
+            Err(_) => PAGE_ERR_JS,
+        },
         Err(e) => error_code(&e),
     }
 }
 
-// -- File upload FFI --
-
-/// Set files on an `<input type="file">` element.
-///
-/// `paths` is a comma-separated list of file paths. Each file is read from disk,
-/// its MIME type inferred from the extension, and injected via the DataTransfer API.
+/// Get an attribute value of an element located by a `Locator` (CSS or XPath), or an
+/// empty string if the element exists but lacks the attribute. `locator_json` is
+/// `{"css":"..."}` or `{"xpath":"..."}`.
 ///
 /// # Safety
 ///
-/// `page`, `selector`, and `paths` must be valid pointers.
+/// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_set_input_files(
+pub unsafe extern "C" fn page_element_attribute_by(
     page: *mut Page,
-    selector: *const std::ffi::c_char,
-    paths: *const std::ffi::c_char,
+    locator_json: *const std::ffi::c_char,
+    attribute: *const std::ffi::c_char,
+    out_value: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
 ) -> i32 {
-    if page.is_null() || selector.is_null() || paths.is_null() {
+    if page.is_null() || attribute.is_null() || out_value.is_null() || out_len.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
-    let page = unsafe { &*page };
-    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
-        Ok(s) => s,
-        Err(_) => return PAGE_ERR_JS,
+    let locator = match unsafe { parse_locator_json(locator_json) } {
+        Ok(locator) => locator,
+        Err(code) => return code,
     };
-    let paths_str = match unsafe { std::ffi::CStr::from_ptr(paths) }.to_str() {
+    let attr = match unsafe { std::ffi::CStr::from_ptr(attribute) }.to_str() {
         Ok(s) => s,
         Err(_) => return PAGE_ERR_JS,
     };
-
-    let mut files = Vec::new();
-    for path_str in paths_str
-        .split(',')
-        .map(|s| s.trim())
-        .filter(|s| !s.is_empty())
-    {
-        let path = std::path::Path::new(path_str);
-        let data = match std::fs::read(path) {
-            Ok(d) => d,
-            Err(_) => return PAGE_ERR_JS,
-        };
-        let name = path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("file")
-            .to_string();
-        let mime_type = match path.extension().and_then(|e| e.to_str()) {
-            Some("txt") => "text/plain",
-            Some("html") | Some("htm") => "text/html",
-            Some("css") => "text/css",
-            Some("js") => "application/javascript",
-            Some("json") => "application/json",
-            Some("xml") => "application/xml",
-            Some("pdf") => "application/pdf",
-            Some("png") => "image/png",
-            Some("jpg") | Some("jpeg") => "image/jpeg",
-            Some("gif") => "image/gif",
-            Some("svg") => "image/svg+xml",
-            Some("webp") => "image/webp",
-            Some("zip") => "application/zip",
-            Some("csv") => "text/csv",
-            _ => "application/octet-stream",
+    let page = unsafe { &*page };
+    match page.element_attribute_by(locator, attr) {
+        Ok(value) => {
+            let s = value.unwrap_or_default();
+            match std::ffi::CString::new(s) {
+                Ok(cstr) => {
+                    let len = cstr.as_bytes().len();
+                    let ptr = cstr.into_raw();
+                    unsafe {
+                        *out_value = ptr;
+                        *out_len = len;
+                    }
+                    PAGE_OK
+                }
+                Err(_) => PAGE_ERR_JS,
+            }
         }
-        .to_string();
-        files.push(InputFile {
-            name,
-            mime_type,
-            data,
-        });
+        Err(e) => error_code(&e),
     }
+}
 
-    match page.set_input_files(sel, files) {
-        Ok(()) => PAGE_OK,
+/// Get the outer HTML of an element located by a `Locator` (CSS or XPath).
+/// `locator_json` is `{"css":"..."}` or `{"xpath":"..."}`.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_element_html_by(
+    page: *mut Page,
+    locator_json: *const std::ffi::c_char,
+    out_html: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || out_html.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let locator = match unsafe { parse_locator_json(locator_json) } {
+        Ok(locator) => locator,
+        Err(code) => return code,
+    };
+    let page = unsafe { &*page };
+    match page.element_html_by(locator) {
+        Ok(html) => match std::ffi::CString::new(html) {
+            Ok(cstr) => {
+                let len = cstr.as_bytes().len();
+                let ptr = cstr.into_raw();
+                unsafe {
+                    *out_html = ptr;
+                    *out_len = len;
+                }
+                PAGE_OK
+            }
+            Err(_) => PAGE_ERR_JS,
+        },
         Err(e) => error_code(&e),
     }
 }
 
-// -- Cookies FFI --
-
-/// Get cookies for the current page.
+/// Get the bounding rectangles of every element matching a CSS selector, as a JSON
+/// array of `[x, y, width, height]` tuples in document order (`[]` if nothing matches).
 ///
-/// On success, `*out_cookies` and `*out_len` are set. Free with `page_string_free()`.
+/// On success, `*out_json` and `*out_len` are set. Free with `page_string_free()`.
 ///
 /// # Safety
 ///
 /// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_get_cookies(
+pub unsafe extern "C" fn page_elements_rect(
     page: *mut Page,
-    out_cookies: *mut *mut std::ffi::c_char,
+    selector: *const std::ffi::c_char,
+    out_json: *mut *mut std::ffi::c_char,
     out_len: *mut usize,
 ) -> i32 {
-    if page.is_null() || out_cookies.is_null() || out_len.is_null() {
+    if page.is_null() || selector.is_null() || out_json.is_null() || out_len.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    match page.get_cookies() {
-        Ok(cookies) => match std::ffi::CString::new(cookies) {
+    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.elements_rect(sel) {
+        Ok(json) => match std::ffi::CString::new(json) {
             Ok(cstr) => {
                 let len = cstr.as_bytes().len();
                 let ptr = cstr.into_raw();
                 unsafe {
-                    *out_cookies = ptr;
+                    *out_json = ptr;
                     *out_len = len;
                 }
                 PAGE_OK
@@ -787,141 +2904,295 @@ pub unsafe extern "C" fn page_get_cookies(
     }
 }
 
-/// Set a cookie via `document.cookie`.
+/// Get the text content of every element matching a CSS selector, as a JSON array of
+/// strings in document order (`[]` if nothing matches).
+///
+/// On success, `*out_json` and `*out_len` are set. Free with `page_string_free()`.
 ///
 /// # Safety
 ///
-/// `page` and `cookie` must be valid pointers.
+/// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_set_cookie(page: *mut Page, cookie: *const std::ffi::c_char) -> i32 {
-    if page.is_null() || cookie.is_null() {
+pub unsafe extern "C" fn page_elements_text(
+    page: *mut Page,
+    selector: *const std::ffi::c_char,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || selector.is_null() || out_json.is_null() || out_len.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    let cookie_str = match unsafe { std::ffi::CStr::from_ptr(cookie) }.to_str() {
+    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
         Ok(s) => s,
         Err(_) => return PAGE_ERR_JS,
     };
-    match page.set_cookie(cookie_str) {
-        Ok(()) => PAGE_OK,
+    match page.elements_text(sel) {
+        Ok(json) => match std::ffi::CString::new(json) {
+            Ok(cstr) => {
+                let len = cstr.as_bytes().len();
+                let ptr = cstr.into_raw();
+                unsafe {
+                    *out_json = ptr;
+                    *out_len = len;
+                }
+                PAGE_OK
+            }
+            Err(_) => PAGE_ERR_JS,
+        },
         Err(e) => error_code(&e),
     }
 }
 
-/// Clear all cookies for the current page.
+/// Get an attribute value of every element matching a CSS selector, as a JSON array
+/// (`[]` if nothing matches; entries are `null` where an element lacks the attribute).
+///
+/// On success, `*out_json` and `*out_len` are set. Free with `page_string_free()`.
 ///
 /// # Safety
 ///
-/// `page` must be a valid pointer.
+/// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_clear_cookies(page: *mut Page) -> i32 {
-    if page.is_null() {
+pub unsafe extern "C" fn page_elements_attribute(
+    page: *mut Page,
+    selector: *const std::ffi::c_char,
+    attribute: *const std::ffi::c_char,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null()
+        || selector.is_null()
+        || attribute.is_null()
+        || out_json.is_null()
+        || out_len.is_null()
+    {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    match page.clear_cookies() {
-        Ok(()) => PAGE_OK,
+    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    let attr = match unsafe { std::ffi::CStr::from_ptr(attribute) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.elements_attribute(sel, attr) {
+        Ok(json) => match std::ffi::CString::new(json) {
+            Ok(cstr) => {
+                let len = cstr.as_bytes().len();
+                let ptr = cstr.into_raw();
+                unsafe {
+                    *out_json = ptr;
+                    *out_len = len;
+                }
+                PAGE_OK
+            }
+            Err(_) => PAGE_ERR_JS,
+        },
         Err(e) => error_code(&e),
     }
 }
 
-// -- Request interception FFI --
-
-/// Set URL patterns to block (comma-separated). Pass NULL to clear.
+/// Get the outer HTML of every element matching a CSS selector, as a JSON array of
+/// strings in document order (`[]` if nothing matches).
+///
+/// On success, `*out_json` and `*out_len` are set. Free with `page_string_free()`.
 ///
 /// # Safety
 ///
-/// `page` must be a valid pointer. `patterns` may be NULL.
+/// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_block_urls(
+pub unsafe extern "C" fn page_elements_html(
     page: *mut Page,
-    patterns: *const std::ffi::c_char,
+    selector: *const std::ffi::c_char,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
 ) -> i32 {
-    if page.is_null() {
+    if page.is_null() || selector.is_null() || out_json.is_null() || out_len.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    if patterns.is_null() {
-        page.clear_blocked_urls();
-    } else {
-        let pat_str = match unsafe { std::ffi::CStr::from_ptr(patterns) }.to_str() {
-            Ok(s) => s,
-            Err(_) => return PAGE_ERR_JS,
-        };
-        let pats: Vec<String> = pat_str
-            .split(',')
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
-            .collect();
-        page.block_urls(pats);
+    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.elements_html(sel) {
+        Ok(json) => match std::ffi::CString::new(json) {
+            Ok(cstr) => {
+                let len = cstr.as_bytes().len();
+                let ptr = cstr.into_raw();
+                unsafe {
+                    *out_json = ptr;
+                    *out_len = len;
+                }
+                PAGE_OK
+            }
+            Err(_) => PAGE_ERR_JS,
+        },
+        Err(e) => error_code(&e),
     }
-    PAGE_OK
 }
 
-// -- Navigation FFI --
-
-/// Reload the current page.
+/// Get rect/text/outer-HTML/attributes for every element matching a CSS selector, as a
+/// JSON array of `ElementInfo` objects, in one round-trip (`[]` if nothing matches).
+///
+/// On success, `*out_json` and `*out_len` are set. Free with `page_string_free()`.
 ///
 /// # Safety
 ///
-/// `page` must be a valid pointer.
+/// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_reload(page: *mut Page) -> i32 {
-    if page.is_null() {
+pub unsafe extern "C" fn page_query_all(
+    page: *mut Page,
+    selector: *const std::ffi::c_char,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || selector.is_null() || out_json.is_null() || out_len.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    match page.reload() {
-        Ok(()) => PAGE_OK,
+    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.query_all(sel) {
+        Ok(infos) => {
+            let json = serde_json::to_string(&infos).unwrap_or_else(|_| "[]".to_string());
+            match std::ffi::CString::new(json) {
+                Ok(cstr) => {
+                    let len = cstr.as_bytes().len();
+                    let ptr = cstr.into_raw();
+                    unsafe {
+                        *out_json = ptr;
+                        *out_len = len;
+                    }
+                    PAGE_OK
+                }
+                Err(_) => PAGE_ERR_JS,
+            }
+        }
         Err(e) => error_code(&e),
     }
 }
 
-/// Navigate back in history. Returns `PAGE_ERR_NO_PAGE` if no history.
+/// Like `page_query_all`, but for just the first matching element, as a single JSON
+/// `ElementInfo` object.
+///
+/// On success, `*out_json` and `*out_len` are set. Free with `page_string_free()`.
 ///
 /// # Safety
 ///
-/// `page` must be a valid pointer.
+/// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_go_back(page: *mut Page) -> i32 {
-    if page.is_null() {
+pub unsafe extern "C" fn page_element_info(
+    page: *mut Page,
+    selector: *const std::ffi::c_char,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || selector.is_null() || out_json.is_null() || out_len.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    match page.go_back() {
-        Ok(true) => PAGE_OK,
-        Ok(false) => PAGE_ERR_NO_PAGE,
+    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.element_info(sel) {
+        Ok(info) => {
+            let json = serde_json::to_string(&info).unwrap_or_else(|_| "{}".to_string());
+            match std::ffi::CString::new(json) {
+                Ok(cstr) => {
+                    let len = cstr.as_bytes().len();
+                    let ptr = cstr.into_raw();
+                    unsafe {
+                        *out_json = ptr;
+                        *out_len = len;
+                    }
+                    PAGE_OK
+                }
+                Err(_) => PAGE_ERR_JS,
+            }
+        }
         Err(e) => error_code(&e),
     }
 }
 
-/// Navigate forward in history. Returns `PAGE_ERR_NO_PAGE` if no forward history.
+// -- Element handle FFI --
+//
+// A handle is passed across the FFI boundary as JSON (`{"id":..,"selector":".."}`),
+// the same round-trip `page_set_cookies_json` uses for `Cookie` — callers just hold
+// onto the string `page_find`/`page_find_all` gave them and pass it back unchanged.
+
+/// Find the first element matching `selector` and write a handle for it to
+/// `*out_json` as JSON. `*out_found` is set to `1` if a match was found, `0`
+/// otherwise (in which case `*out_json`/`*out_len` are not set).
+///
+/// On success, free `*out_json` with `page_string_free()`.
 ///
 /// # Safety
 ///
-/// `page` must be a valid pointer.
+/// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_go_forward(page: *mut Page) -> i32 {
-    if page.is_null() {
+pub unsafe extern "C" fn page_find(
+    page: *mut Page,
+    selector: *const std::ffi::c_char,
+    out_found: *mut i32,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null()
+        || selector.is_null()
+        || out_found.is_null()
+        || out_json.is_null()
+        || out_len.is_null()
+    {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    match page.go_forward() {
-        Ok(true) => PAGE_OK,
-        Ok(false) => PAGE_ERR_NO_PAGE,
+    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.find(sel) {
+        Ok(Some(handle)) => {
+            let json = serde_json::to_string(&handle).unwrap_or_else(|_| "{}".to_string());
+            match std::ffi::CString::new(json) {
+                Ok(cstr) => {
+                    let len = cstr.as_bytes().len();
+                    let ptr = cstr.into_raw();
+                    unsafe {
+                        *out_found = 1;
+                        *out_json = ptr;
+                        *out_len = len;
+                    }
+                    PAGE_OK
+                }
+                Err(_) => PAGE_ERR_JS,
+            }
+        }
+        Ok(None) => {
+            unsafe {
+                *out_found = 0;
+            }
+            PAGE_OK
+        }
         Err(e) => error_code(&e),
     }
 }
 
-// -- Element info FFI --
-
-/// Get the bounding rectangle of an element as JSON (`{"x":..,"y":..,"width":..,"height":..}`).
+/// Find every element matching `selector` and write a JSON array of handles to
+/// `*out_json` (`[]` if nothing matches).
+///
+/// On success, `*out_json` and `*out_len` are set. Free with `page_string_free()`.
 ///
 /// # Safety
 ///
 /// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_element_rect(
+pub unsafe extern "C" fn page_find_all(
     page: *mut Page,
     selector: *const std::ffi::c_char,
     out_json: *mut *mut std::ffi::c_char,
@@ -935,9 +3206,9 @@ pub unsafe extern "C" fn page_element_rect(
         Ok(s) => s,
         Err(_) => return PAGE_ERR_JS,
     };
-    match page.element_rect(sel) {
-        Ok(rect) => {
-            let json = serde_json::to_string(&rect).unwrap_or_else(|_| "{}".to_string());
+    match page.find_all(sel) {
+        Ok(handles) => {
+            let json = serde_json::to_string(&handles).unwrap_or_else(|_| "[]".to_string());
             match std::ffi::CString::new(json) {
                 Ok(cstr) => {
                     let len = cstr.as_bytes().len();
@@ -955,27 +3226,42 @@ pub unsafe extern "C" fn page_element_rect(
     }
 }
 
-/// Get the text content of an element.
+/// Parse a handle JSON string written by `page_find`/`page_find_all`. Returns
+/// `PAGE_ERR_JS` for malformed JSON, via the same error path as a JS failure.
+///
+/// # Safety
+///
+/// `handle_json` must be a valid pointer.
+unsafe fn parse_handle_json(
+    handle_json: *const std::ffi::c_char,
+) -> Result<crate::types::ElementHandle, i32> {
+    let json = unsafe { std::ffi::CStr::from_ptr(handle_json) }
+        .to_str()
+        .map_err(|_| PAGE_ERR_JS)?;
+    serde_json::from_str(json).map_err(|_| PAGE_ERR_JS)
+}
+
+/// Get the text content of the element a handle points to.
 ///
 /// # Safety
 ///
 /// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_element_text(
+pub unsafe extern "C" fn page_handle_text(
     page: *mut Page,
-    selector: *const std::ffi::c_char,
+    handle_json: *const std::ffi::c_char,
     out_text: *mut *mut std::ffi::c_char,
     out_len: *mut usize,
 ) -> i32 {
-    if page.is_null() || selector.is_null() || out_text.is_null() || out_len.is_null() {
+    if page.is_null() || handle_json.is_null() || out_text.is_null() || out_len.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
-        Ok(s) => s,
-        Err(_) => return PAGE_ERR_JS,
+    let handle = match unsafe { parse_handle_json(handle_json) } {
+        Ok(h) => h,
+        Err(code) => return code,
     };
-    match page.element_text(sel) {
+    match page.handle_text(&handle) {
         Ok(text) => match std::ffi::CString::new(text) {
             Ok(cstr) => {
                 let len = cstr.as_bytes().len();
@@ -992,21 +3278,22 @@ pub unsafe extern "C" fn page_element_text(
     }
 }
 
-/// Get an attribute value of an element. Returns empty string if attribute doesn't exist.
+/// Get an attribute value of the element a handle points to. Returns empty string if
+/// the attribute doesn't exist.
 ///
 /// # Safety
 ///
 /// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_element_attribute(
+pub unsafe extern "C" fn page_handle_attribute(
     page: *mut Page,
-    selector: *const std::ffi::c_char,
+    handle_json: *const std::ffi::c_char,
     attribute: *const std::ffi::c_char,
     out_value: *mut *mut std::ffi::c_char,
     out_len: *mut usize,
 ) -> i32 {
     if page.is_null()
-        || selector.is_null()
+        || handle_json.is_null()
         || attribute.is_null()
         || out_value.is_null()
         || out_len.is_null()
@@ -1014,15 +3301,15 @@ pub unsafe extern "C" fn page_element_attribute(
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
-        Ok(s) => s,
-        Err(_) => return PAGE_ERR_JS,
+    let handle = match unsafe { parse_handle_json(handle_json) } {
+        Ok(h) => h,
+        Err(code) => return code,
     };
     let attr = match unsafe { std::ffi::CStr::from_ptr(attribute) }.to_str() {
         Ok(s) => s,
         Err(_) => return PAGE_ERR_JS,
     };
-    match page.element_attribute(sel, attr) {
+    match page.handle_attribute(&handle, attr) {
         Ok(value) => {
             let s = value.unwrap_or_default();
             match std::ffi::CString::new(s) {
@@ -1042,33 +3329,135 @@ pub unsafe extern "C" fn page_element_attribute(
     }
 }
 
-/// Get the outer HTML of an element.
+/// Get the bounding rectangle of the element a handle points to, as JSON
+/// (`{"x":..,"y":..,"width":..,"height":..}`).
+///
+/// On success, `*out_json` and `*out_len` are set. Free with `page_string_free()`.
 ///
 /// # Safety
 ///
 /// All pointer arguments must be valid or NULL.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_element_html(
+pub unsafe extern "C" fn page_handle_bounding_box(
     page: *mut Page,
-    selector: *const std::ffi::c_char,
-    out_html: *mut *mut std::ffi::c_char,
+    handle_json: *const std::ffi::c_char,
+    out_json: *mut *mut std::ffi::c_char,
     out_len: *mut usize,
 ) -> i32 {
-    if page.is_null() || selector.is_null() || out_html.is_null() || out_len.is_null() {
+    if page.is_null() || handle_json.is_null() || out_json.is_null() || out_len.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    let sel = match unsafe { std::ffi::CStr::from_ptr(selector) }.to_str() {
+    let handle = match unsafe { parse_handle_json(handle_json) } {
+        Ok(h) => h,
+        Err(code) => return code,
+    };
+    match page.handle_bounding_box(&handle) {
+        Ok(rect) => {
+            let json = serde_json::to_string(&rect).unwrap_or_else(|_| "{}".to_string());
+            match std::ffi::CString::new(json) {
+                Ok(cstr) => {
+                    let len = cstr.as_bytes().len();
+                    let ptr = cstr.into_raw();
+                    unsafe {
+                        *out_json = ptr;
+                        *out_len = len;
+                    }
+                    PAGE_OK
+                }
+                Err(_) => PAGE_ERR_JS,
+            }
+        }
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Click the element a handle points to, at its current on-screen position.
+///
+/// # Safety
+///
+/// `page` and `handle_json` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_handle_click(
+    page: *mut Page,
+    handle_json: *const std::ffi::c_char,
+) -> i32 {
+    if page.is_null() || handle_json.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let handle = match unsafe { parse_handle_json(handle_json) } {
+        Ok(h) => h,
+        Err(code) => return code,
+    };
+    match page.handle_click(&handle) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Click the element a handle points to, then type text into it.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_handle_type_text(
+    page: *mut Page,
+    handle_json: *const std::ffi::c_char,
+    text: *const std::ffi::c_char,
+) -> i32 {
+    if page.is_null() || handle_json.is_null() || text.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let handle = match unsafe { parse_handle_json(handle_json) } {
+        Ok(h) => h,
+        Err(code) => return code,
+    };
+    let text_str = match unsafe { std::ffi::CStr::from_ptr(text) }.to_str() {
         Ok(s) => s,
         Err(_) => return PAGE_ERR_JS,
     };
-    match page.element_html(sel) {
-        Ok(html) => match std::ffi::CString::new(html) {
+    match page.handle_type_text(&handle, text_str) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Resolve a caller-supplied extraction spec and the page's URL/title in a single
+/// call. `spec_json` is a JSON object mapping field name to
+/// `{selector, kind: "text"|"attr"|"html"|"rect", attribute?}`. On success, writes a
+/// JSON object `{url, title, fields: {name: value, ...}}` to `*out_json`; a field is
+/// `null` if its selector matched nothing.
+///
+/// On success, `*out_json` and `*out_len` are set. Free with `page_string_free()`.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_snapshot(
+    page: *mut Page,
+    spec_json: *const std::ffi::c_char,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || spec_json.is_null() || out_json.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let spec = match unsafe { std::ffi::CStr::from_ptr(spec_json) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.snapshot(spec) {
+        Ok(json) => match std::ffi::CString::new(json) {
             Ok(cstr) => {
                 let len = cstr.as_bytes().len();
                 let ptr = cstr.into_raw();
                 unsafe {
-                    *out_html = ptr;
+                    *out_json = ptr;
                     *out_len = len;
                 }
                 PAGE_OK
@@ -1128,36 +3517,82 @@ pub unsafe extern "C" fn page_new_page_with_size(
     }
 }
 
-/// Switch the active page to the given ID.
+/// Switch the active page to the given ID.
+///
+/// # Safety
+///
+/// `page` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_switch_to(page: *mut Page, page_id: u32) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.switch_to(page_id) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Close a specific page by ID.
+///
+/// # Safety
+///
+/// `page` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_close_page(page: *mut Page, page_id: u32) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.close_page(page_id) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Tear down a non-active page's document/layout state to reclaim memory. See
+/// `Page::discard_page`.
 ///
 /// # Safety
 ///
 /// `page` must be a valid pointer.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_switch_to(page: *mut Page, page_id: u32) -> i32 {
+pub unsafe extern "C" fn page_discard_page(page: *mut Page, page_id: u32) -> i32 {
     if page.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    match page.switch_to(page_id) {
+    match page.discard_page(page_id) {
         Ok(()) => PAGE_OK,
         Err(e) => error_code(&e),
     }
 }
 
-/// Close a specific page by ID.
+/// Query a page's lifecycle state. On success, `*out_discarded` is `1` if the page has
+/// been discarded (see `page_discard_page`) or `0` if it's still live. See
+/// `Page::page_state`.
 ///
 /// # Safety
 ///
-/// `page` must be a valid pointer.
+/// `page` and `out_discarded` must be valid pointers.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn page_close_page(page: *mut Page, page_id: u32) -> i32 {
-    if page.is_null() {
+pub unsafe extern "C" fn page_page_state(
+    page: *mut Page,
+    page_id: u32,
+    out_discarded: *mut i32,
+) -> i32 {
+    if page.is_null() || out_discarded.is_null() {
         return PAGE_ERR_NULL_PTR;
     }
     let page = unsafe { &*page };
-    match page.close_page(page_id) {
-        Ok(()) => PAGE_OK,
+    match page.page_state(page_id) {
+        Ok(state) => {
+            unsafe {
+                *out_discarded = matches!(state, crate::types::PageLifecycle::Discarded) as i32;
+            }
+            PAGE_OK
+        }
         Err(e) => error_code(&e),
     }
 }
@@ -1245,6 +3680,44 @@ pub unsafe extern "C" fn page_set_popup_handling(page: *mut Page, enabled: i32)
     PAGE_OK
 }
 
+/// Set how popups are handled: `0` = block, `1` = capture (see `page_popup_pages`),
+/// `2` = redirect the opener's own WebView to the popup's URL instead of creating a
+/// new page. Unrecognized codes are treated as `0` (block).
+///
+/// # Safety
+///
+/// `page` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_set_popup_policy(page: *mut Page, policy: i32) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let policy = match policy {
+        1 => crate::types::PopupPolicy::Capture,
+        2 => crate::types::PopupPolicy::Redirect,
+        _ => crate::types::PopupPolicy::Block,
+    };
+    page.set_popup_policy(policy);
+    PAGE_OK
+}
+
+/// Enable or disable response-body capture at runtime. Pass non-zero to enable. See
+/// `PageEngine::capture_response_bodies`.
+///
+/// # Safety
+///
+/// `page` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_capture_response_bodies(page: *mut Page, enabled: i32) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    page.capture_response_bodies(enabled != 0);
+    PAGE_OK
+}
+
 /// Drain pending popup pages and return their IDs as a JSON array.
 /// Free the result with `page_string_free()`.
 ///
@@ -1345,6 +3818,309 @@ pub unsafe extern "C" fn page_page_title(
     }
 }
 
+/// Render a specific page's current viewport to a PDF, without switching the active
+/// page. Same WebDriver-style print parameters as [`page_print_to_pdf`]; see there for
+/// the single-page-per-viewport caveat.
+///
+/// On success, `*out_data` and `*out_len` are set. Free with `page_buffer_free()`.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL. `page_ranges`, if not NULL, must be a
+/// valid C string.
+#[unsafe(no_mangle)]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn page_page_to_pdf(
+    page: *mut Page,
+    page_id: u32,
+    paper_width: f64,
+    paper_height: f64,
+    margin_top: f64,
+    margin_bottom: f64,
+    margin_left: f64,
+    margin_right: f64,
+    landscape: i32,
+    scale: f64,
+    background: i32,
+    page_ranges: *const std::ffi::c_char,
+    prefer_css_page_size: i32,
+    out_data: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || out_data.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let page_ranges = if page_ranges.is_null() {
+        None
+    } else {
+        match unsafe { std::ffi::CStr::from_ptr(page_ranges) }.to_str() {
+            Ok(s) => parse_page_ranges(s),
+            Err(_) => return PAGE_ERR_JS,
+        }
+    };
+    let opts = PdfOptions {
+        paper_width,
+        paper_height,
+        margin_top,
+        margin_bottom,
+        margin_left,
+        margin_right,
+        orientation: if landscape != 0 {
+            Orientation::Landscape
+        } else {
+            Orientation::Portrait
+        },
+        scale,
+        background: background != 0,
+        page_ranges,
+        prefer_css_page_size: prefer_css_page_size != 0,
+    };
+    match page.page_to_pdf(page_id, opts) {
+        Ok(pdf_bytes) => {
+            let boxed = pdf_bytes.into_boxed_slice();
+            let len = boxed.len();
+            let ptr = Box::into_raw(boxed) as *mut u8;
+            unsafe {
+                *out_data = ptr;
+                *out_len = len;
+            }
+            PAGE_OK
+        }
+        Err(e) => error_code(&e),
+    }
+}
+
+// -- Find FFI --
+
+/// Search the rendered text of the page, highlighting every match and scrolling the
+/// first one into view. `flags` is a bitmask of `find_flags` values from
+/// [`crate::types`]. On success, `*out_match_count` is set to the total match count.
+///
+/// # Safety
+///
+/// `page`, `query`, and `out_match_count` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_find_text(
+    page: *mut Page,
+    query: *const std::ffi::c_char,
+    flags: u32,
+    out_match_count: *mut u32,
+) -> i32 {
+    if page.is_null() || query.is_null() || out_match_count.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let query_str = match unsafe { std::ffi::CStr::from_ptr(query) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.find_text(query_str, flags) {
+        Ok(count) => {
+            unsafe {
+                *out_match_count = count;
+            }
+            PAGE_OK
+        }
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Advance to the next match from a prior `page_find_text()` call. On success,
+/// `*out_json` and `*out_len` are set to its bounding rectangle as JSON
+/// (`{"x":..,"y":..,"width":..,"height":..}`), freshly scrolled into view.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_find_next(
+    page: *mut Page,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || out_json.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.find_next() {
+        Ok(rect) => {
+            let json = serde_json::to_string(&rect).unwrap_or_else(|_| "{}".to_string());
+            match std::ffi::CString::new(json) {
+                Ok(cstr) => {
+                    let len = cstr.as_bytes().len();
+                    let ptr = cstr.into_raw();
+                    unsafe {
+                        *out_json = ptr;
+                        *out_len = len;
+                    }
+                    PAGE_OK
+                }
+                Err(_) => PAGE_ERR_JS,
+            }
+        }
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Move to the previous match from a prior `page_find_text()` call. On success,
+/// `*out_json` and `*out_len` are set to its bounding rectangle as JSON
+/// (`{"x":..,"y":..,"width":..,"height":..}`), freshly scrolled into view.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_find_previous(
+    page: *mut Page,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || out_json.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.find_previous() {
+        Ok(rect) => {
+            let json = serde_json::to_string(&rect).unwrap_or_else(|_| "{}".to_string());
+            match std::ffi::CString::new(json) {
+                Ok(cstr) => {
+                    let len = cstr.as_bytes().len();
+                    let ptr = cstr.into_raw();
+                    unsafe {
+                        *out_json = ptr;
+                        *out_len = len;
+                    }
+                    PAGE_OK
+                }
+                Err(_) => PAGE_ERR_JS,
+            }
+        }
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Remove all highlights left by `page_find_text()`.
+///
+/// # Safety
+///
+/// `page` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_find_clear(page: *mut Page) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.find_clear() {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+// -- Download capture FFI --
+
+/// Arm (`enabled != 0`) or disarm download capture.
+///
+/// # Safety
+///
+/// `page` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_set_download_capture(page: *mut Page, enabled: i32) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.set_download_capture(enabled != 0) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Block until at least `count` downloads have been captured, or `timeout_secs` elapses.
+/// See [`crate::engine::PageEngine::wait_for_downloads`] for why this matters: a
+/// download is buffered asynchronously, so calling `page_get_downloads` right after
+/// triggering one can race ahead of the capture and see an incomplete list.
+///
+/// # Safety
+///
+/// `page` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_wait_for_downloads(
+    page: *mut Page,
+    count: usize,
+    timeout_secs: u64,
+) -> i32 {
+    if page.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.wait_for_downloads(count, timeout_secs) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Get captured downloads as a JSON array of `{suggested_filename, mime_type, url,
+/// size}` objects.
+///
+/// On success, `*out_json` and `*out_len` are set. Free with `page_string_free()`.
+///
+/// # Safety
+///
+/// All pointer arguments must be valid or NULL.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_get_downloads(
+    page: *mut Page,
+    out_json: *mut *mut std::ffi::c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if page.is_null() || out_json.is_null() || out_len.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    match page.get_downloads() {
+        Ok(json) => match std::ffi::CString::new(json) {
+            Ok(cstr) => {
+                let len = cstr.as_bytes().len();
+                let ptr = cstr.into_raw();
+                unsafe {
+                    *out_json = ptr;
+                    *out_len = len;
+                }
+                PAGE_OK
+            }
+            Err(_) => PAGE_ERR_JS,
+        },
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Flush the buffered bytes of a captured download (by its index in
+/// `page_get_downloads()`) to `dest_path` on disk.
+///
+/// # Safety
+///
+/// `page` and `dest_path` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn page_save_download(
+    page: *mut Page,
+    index: u32,
+    dest_path: *const std::ffi::c_char,
+) -> i32 {
+    if page.is_null() || dest_path.is_null() {
+        return PAGE_ERR_NULL_PTR;
+    }
+    let page = unsafe { &*page };
+    let path_str = match unsafe { std::ffi::CStr::from_ptr(dest_path) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return PAGE_ERR_JS,
+    };
+    match page.save_download(index, path_str) {
+        Ok(()) => PAGE_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
 // -- Memory --
 
 /// Free a buffer returned by `page_screenshot()` or `page_screenshot_fullpage()`.
@@ -4,15 +4,21 @@
 
 //! A minimal headless utility for web scraping using Servo.
 //!
-//! Supports capturing screenshots and/or HTML content from web pages.
+//! Supports capturing screenshots, HTML content, JS console output, and a network
+//! request log from web pages.
 //!
 //! ```bash
 //! servo-scraper --screenshot page.png https://example.com
 //! servo-scraper --html page.html https://example.com
 //! servo-scraper --screenshot page.png --html page.html --width 1920 --height 1080 https://example.com
+//! servo-scraper --html page.html --console console.json https://example.com
+//! servo-scraper --html page.html --har requests.json https://example.com
+//! servo-scraper --screenshot out/{index}.png https://example.com https://example.org
+//! servo-scraper --screenshot out/{index}.png --url-file urls.txt
 //! ```
 
 use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::os::fd::{AsRawFd, IntoRawFd};
 use std::path::PathBuf;
 use std::process;
@@ -22,13 +28,16 @@ use std::time::{Duration, Instant};
 
 use bpaf::Bpaf;
 use dpi::PhysicalSize;
-use image::{DynamicImage, ImageFormat};
+use image::codecs::jpeg::JpegEncoder;
+use image::{DynamicImage, ImageEncoder, ImageFormat};
 use log::error;
 use servo::resources::{self, Resource, ResourceReaderMethods};
 use servo::{
-    EventLoopWaker, JSValue, JavaScriptEvaluationError, LoadStatus, RenderingContext, Servo,
-    ServoBuilder, SoftwareRenderingContext, WebView, WebViewBuilder, WebViewDelegate,
+    ConsoleLogLevel, EventLoopWaker, JSValue, JavaScriptEvaluationError, LoadStatus,
+    RenderingContext, Servo, ServoBuilder, SoftwareRenderingContext, WebResourceLoad, WebView,
+    WebViewBuilder, WebViewDelegate,
 };
+use serde::{Deserialize, Serialize};
 use url::Url;
 
 // ---------------------------------------------------------------------------
@@ -104,16 +113,38 @@ impl ResourceReaderMethods for EmbeddedResourceReader {
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Clone, Bpaf)]
-#[bpaf(options, usage("servo-scraper [OPTIONS] <URL>"))]
+#[bpaf(options, usage("servo-scraper [OPTIONS] <URL>..."))]
 struct ScraperConfig {
-    /// Save a screenshot to the given file (png, jpg, bmp)
+    /// Save a screenshot to the given file (png, jpg, bmp, webp)
     #[bpaf(long, short, argument("PATH"))]
     screenshot: Option<String>,
 
+    /// Crop the screenshot to this sub-rectangle, e.g. `100,50,400,300`
+    #[bpaf(long, argument("X,Y,W,H"))]
+    clip: Option<String>,
+
+    /// Crop the screenshot to the bounding box of the first element matching this CSS
+    /// selector. Takes precedence over --clip if both are given
+    #[bpaf(long, argument("CSS"))]
+    clip_selector: Option<String>,
+
+    /// JPEG quality (1-100) when --screenshot is saved as a .jpg/.jpeg file
+    #[bpaf(long, argument("1-100"))]
+    jpeg_quality: Option<u8>,
+
     /// Save the page HTML to the given file
     #[bpaf(long, argument("PATH"))]
     html: Option<String>,
 
+    /// Save captured JS console messages (log/warn/error/info) as a JSON array to the given file
+    #[bpaf(long, argument("PATH"))]
+    console: Option<String>,
+
+    /// Save every observed network request (method, URL, main-frame flag, timing) as a
+    /// JSON array to the given file
+    #[bpaf(long, argument("PATH"))]
+    har: Option<String>,
+
     /// Viewport width in pixels
     #[bpaf(long, argument("PIXELS"), fallback(1280u32))]
     width: u32,
@@ -130,19 +161,144 @@ struct ScraperConfig {
     #[bpaf(long, argument("SECONDS"), fallback(2.0f64))]
     wait: f64,
 
+    /// Instead of the fixed --wait delay, wait for network requests to go quiet for
+    /// this many milliseconds (bounded by --timeout) before capturing -- more reliable
+    /// for SPAs that fetch data after the load event
+    #[bpaf(long, argument("MILLIS"))]
+    wait_idle: Option<u64>,
+
     /// Capture the full scrollable page, not just the viewport
     #[bpaf(long, short)]
     fullpage: bool,
 
-    /// URL to load
-    #[bpaf(positional::<String>("URL"), parse(parse_url))]
-    url: Url,
+    /// Extract every element matching a CSS selector as outerHTML/textContent (and
+    /// optionally an attribute, written as `CSS:attr`). Repeatable; results are
+    /// written to --select-output keyed by selector
+    #[bpaf(long, argument("CSS[:attr]"))]
+    select: Vec<String>,
+
+    /// Where to write the --select results as JSON (defaults to stdout)
+    #[bpaf(long, argument("PATH"))]
+    select_output: Option<String>,
+
+    /// Exit with a nonzero status if any --select selector matches nothing
+    #[bpaf(long)]
+    require_selectors: bool,
+
+    /// Run this JavaScript after the page settles but before capture; the returned
+    /// value is printed to stdout as JSON
+    #[bpaf(long, argument("SCRIPT"))]
+    eval: Option<String>,
+
+    /// Like --eval, but read the script from a file
+    #[bpaf(long, argument("PATH"))]
+    eval_file: Option<String>,
+
+    /// Replace the document's contents with the HTML in this file (via
+    /// document.open()/write()/close()) before --eval and capture
+    #[bpaf(long, argument("PATH"))]
+    inject_html: Option<String>,
+
+    /// Read additional URLs from this file, one per line (blank lines and lines
+    /// starting with `#` are ignored). Use `-` to read from stdin. Combined with any
+    /// positional URLs -- positional URLs are processed first, then file/stdin ones
+    #[bpaf(long, argument("PATH"))]
+    url_file: Option<String>,
+
+    /// URL(s) to scrape. A single Servo instance and WebView are reused across all of
+    /// them, navigating sequentially -- much cheaper than one process per URL. When
+    /// more than one URL is given (here and/or via --url-file), output paths
+    /// (--screenshot, --html, --console, --har, --select-output) may include
+    /// `{index}` -- the 0-based position of the URL in the batch -- so each page
+    /// doesn't overwrite the last, e.g. `out/{index}.png`
+    #[bpaf(positional::<String>("URL"), many)]
+    urls: Vec<String>,
 }
 
 fn parse_url(s: String) -> Result<Url, String> {
     Url::parse(&s).map_err(|e| format!("Invalid URL: {e}"))
 }
 
+/// Read `--url-file`'s URL list: one per line, blank lines and `#`-comments skipped.
+/// `path == "-"` reads from stdin instead of a file, for piping in a URL list.
+fn read_url_list(path: &str) -> std::io::Result<Vec<String>> {
+    let content = if path == "-" {
+        let mut buf = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut buf)?;
+        buf
+    } else {
+        std::fs::read_to_string(path)?
+    };
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Substitute `{index}` in an output path (e.g. `--screenshot`) with the 0-based
+/// position of the current URL in a multi-URL run. A no-op for single-URL runs whose
+/// path doesn't contain the placeholder.
+fn apply_index_template(path: &str, index: usize) -> String {
+    path.replace("{index}", &index.to_string())
+}
+
+/// Parse a `--clip` spec of the form `X,Y,W,H` into its four components.
+fn parse_clip_spec(spec: &str) -> Option<(u32, u32, u32, u32)> {
+    let parts: Vec<&str> = spec.split(',').collect();
+    if parts.len() != 4 {
+        return None;
+    }
+    let x = parts[0].trim().parse().ok()?;
+    let y = parts[1].trim().parse().ok()?;
+    let width = parts[2].trim().parse().ok()?;
+    let height = parts[3].trim().parse().ok()?;
+    Some((x, y, width, height))
+}
+
+/// A console message captured from the page, written to `--console` as a JSON array.
+#[derive(Debug, Clone, Serialize)]
+struct ConsoleMessage {
+    level: String,
+    message: String,
+}
+
+/// A single element matched by a `--select` CSS selector.
+#[derive(Debug, Clone, Serialize)]
+struct SelectorMatch {
+    outer_html: String,
+    text: String,
+    attribute: Option<String>,
+}
+
+/// Split a `--select` spec of the form `CSS` or `CSS:attr` into its selector and
+/// optional attribute name. Splits on the *last* colon, so a selector ending in a
+/// pseudo-class whose name happens to look like an attribute (e.g. `a:hover`) will be
+/// misread as `a` with attribute `hover` -- avoid trailing pseudo-classes when using
+/// the `:attr` suffix.
+fn parse_select_spec(spec: &str) -> (&str, Option<&str>) {
+    match spec.rsplit_once(':') {
+        Some((selector, attr)) if !selector.is_empty() && !attr.is_empty() => {
+            (selector, Some(attr))
+        },
+        _ => (spec, None),
+    }
+}
+
+/// A network request observed via the WebView delegate's resource-load notification,
+/// written to `--har` as a JSON array. Status/headers aren't recorded since this
+/// delegate never intercepts loads -- Servo's embedder API gives no hook to observe
+/// the response of a request it didn't fulfill itself.
+#[derive(Debug, Clone, Serialize)]
+struct NetworkRequest {
+    method: String,
+    url: String,
+    is_main_frame: bool,
+    /// Milliseconds since the WebView was created when this request was observed.
+    observed_at_ms: u64,
+}
+
 // ---------------------------------------------------------------------------
 // Event loop (condvar-based, pattern from servoshell HeadlessEventLoop)
 // ---------------------------------------------------------------------------
@@ -235,13 +391,64 @@ fn spin_for(servo: &Servo, event_loop: &ScraperEventLoop, duration: Duration) {
     }
 }
 
+/// Wait until no new network requests have been observed for `idle_duration`, or
+/// `max_timeout` elapses. Only request *starts* are tracked -- `load_web_resource`
+/// never intercepts, so there's no hook to learn when a pass-through request
+/// finishes -- but in practice new requests keep landing for as long as a page has
+/// in-flight fetches, so a quiet window on starts is a reliable enough proxy.
+fn spin_until_network_idle(
+    servo: &Servo,
+    event_loop: &ScraperEventLoop,
+    delegate: &ScraperDelegate,
+    idle_duration: Duration,
+    max_timeout: Duration,
+) {
+    let max_deadline = Instant::now() + max_timeout;
+    let mut idle_deadline = Instant::now() + idle_duration;
+    let mut last_seen = delegate.last_request_at.get();
+    loop {
+        event_loop.sleep();
+        servo.spin_event_loop();
+        event_loop.clear();
+        let now = Instant::now();
+        let current = delegate.last_request_at.get();
+        if current != last_seen {
+            last_seen = current;
+            idle_deadline = now + idle_duration;
+        }
+        if now >= idle_deadline {
+            return;
+        }
+        if now >= max_deadline {
+            eprintln!("Warning: timed out waiting for network idle");
+            return;
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // WebView delegate
 // ---------------------------------------------------------------------------
 
-#[derive(Default)]
 struct ScraperDelegate {
     load_complete: Cell<bool>,
+    console_messages: RefCell<Vec<ConsoleMessage>>,
+    network_requests: RefCell<Vec<NetworkRequest>>,
+    created_at: Instant,
+    /// When the most recent network request was observed, for `--wait-idle`.
+    last_request_at: Cell<Option<Instant>>,
+}
+
+impl Default for ScraperDelegate {
+    fn default() -> Self {
+        ScraperDelegate {
+            load_complete: Cell::new(false),
+            console_messages: RefCell::new(Vec::new()),
+            network_requests: RefCell::new(Vec::new()),
+            created_at: Instant::now(),
+            last_request_at: Cell::new(None),
+        }
+    }
 }
 
 impl WebViewDelegate for ScraperDelegate {
@@ -255,6 +462,33 @@ impl WebViewDelegate for ScraperDelegate {
         // Paint is required so that screenshots contain actual content.
         webview.paint();
     }
+
+    fn show_console_message(&self, _webview: WebView, level: ConsoleLogLevel, message: String) {
+        let level_str = match level {
+            ConsoleLogLevel::Log => "log",
+            ConsoleLogLevel::Debug => "debug",
+            ConsoleLogLevel::Info => "info",
+            ConsoleLogLevel::Warn => "warn",
+            ConsoleLogLevel::Error => "error",
+            ConsoleLogLevel::Trace => "trace",
+        };
+        self.console_messages.borrow_mut().push(ConsoleMessage {
+            level: level_str.to_string(),
+            message,
+        });
+    }
+
+    fn load_web_resource(&self, _webview: WebView, load: WebResourceLoad) {
+        let request = load.request();
+        self.network_requests.borrow_mut().push(NetworkRequest {
+            method: request.method.to_string(),
+            url: request.url.to_string(),
+            is_main_frame: request.is_for_main_frame,
+            observed_at_ms: self.created_at.elapsed().as_millis() as u64,
+        });
+        self.last_request_at.set(Some(Instant::now()));
+        // Drop `load` without calling `.intercept()` so it continues normally.
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -280,11 +514,25 @@ fn eval_js(
     result.borrow_mut().take()
 }
 
+/// Crop `image` to the axis-aligned region `(x, y, width, height)`, clamped to the
+/// image bounds rather than erroring, so a clip/element rect that runs slightly past
+/// the edge (e.g. from sub-pixel layout) still produces a usable, if smaller, image.
+fn crop_image(image: DynamicImage, x: u32, y: u32, width: u32, height: u32) -> DynamicImage {
+    let (img_w, img_h) = (image.width(), image.height());
+    let x = x.min(img_w.saturating_sub(1));
+    let y = y.min(img_h.saturating_sub(1));
+    let clipped_width = width.min(img_w - x).max(1);
+    let clipped_height = height.min(img_h - y).max(1);
+    image.crop_imm(x, y, clipped_width, clipped_height)
+}
+
 fn take_screenshot_to_file(
     servo: &Servo,
     event_loop: &ScraperEventLoop,
     webview: &WebView,
     path: &str,
+    clip: Option<(u32, u32, u32, u32)>,
+    jpeg_quality: Option<u8>,
 ) {
     let result: Rc<RefCell<Option<Result<servo::RgbaImage, _>>>> = Rc::new(RefCell::new(None));
     let cb_result = result.clone();
@@ -298,8 +546,26 @@ fn take_screenshot_to_file(
     let image_result = result.borrow_mut().take();
     match image_result {
         Some(Ok(image)) => {
+            let mut image = DynamicImage::ImageRgba8(image);
+            if let Some((x, y, width, height)) = clip {
+                image = crop_image(image, x, y, width, height);
+            }
             let format = ImageFormat::from_path(path).unwrap_or(ImageFormat::Png);
-            if let Err(e) = DynamicImage::ImageRgba8(image).save_with_format(path, format) {
+            let save_result = match (format, jpeg_quality) {
+                (ImageFormat::Jpeg, Some(quality)) => std::fs::File::create(path)
+                    .map_err(image::ImageError::IoError)
+                    .and_then(|file| {
+                        let rgb8 = image.to_rgb8();
+                        JpegEncoder::new_with_quality(file, quality).write_image(
+                            &rgb8,
+                            rgb8.width(),
+                            rgb8.height(),
+                            image::ExtendedColorType::Rgb8,
+                        )
+                    }),
+                _ => image.save_with_format(path, format),
+            };
+            if let Err(e) = save_result {
                 error!("Failed to save screenshot to {path}: {e}");
                 eprintln!("Error: failed to save screenshot: {e}");
             } else {
@@ -316,6 +582,120 @@ fn take_screenshot_to_file(
     }
 }
 
+/// Render a `JSValue` as JSON text, for `--eval`'s stdout output.
+fn jsvalue_to_json(value: &JSValue) -> String {
+    match value {
+        JSValue::Undefined => "undefined".to_string(),
+        JSValue::Null => "null".to_string(),
+        JSValue::Boolean(b) => serde_json::to_string(b).unwrap(),
+        JSValue::Number(n) => serde_json::to_string(n).unwrap(),
+        JSValue::String(s) => serde_json::to_string(s).unwrap(),
+        JSValue::Array(arr) => {
+            let items: Vec<String> = arr.iter().map(jsvalue_to_json).collect();
+            format!("[{}]", items.join(","))
+        },
+        JSValue::Object(map) => {
+            let entries: Vec<String> = map
+                .iter()
+                .map(|(k, v)| format!("{}:{}", serde_json::to_string(k).unwrap(), jsvalue_to_json(v)))
+                .collect();
+            format!("{{{}}}", entries.join(","))
+        },
+        // Element, ShadowRoot, Frame, Window — return as JSON string with type prefix.
+        JSValue::Element(id) => serde_json::to_string(&format!("[Element:{id}]")).unwrap(),
+        JSValue::ShadowRoot(id) => serde_json::to_string(&format!("[ShadowRoot:{id}]")).unwrap(),
+        JSValue::Frame(id) => serde_json::to_string(&format!("[Frame:{id}]")).unwrap(),
+        JSValue::Window(id) => serde_json::to_string(&format!("[Window:{id}]")).unwrap(),
+    }
+}
+
+/// Run user-supplied JavaScript and print the result to stdout as JSON.
+fn run_eval_script(servo: &Servo, event_loop: &ScraperEventLoop, webview: &WebView, script: &str) {
+    match eval_js(servo, event_loop, webview, script) {
+        Some(Ok(value)) => println!("{}", jsvalue_to_json(&value)),
+        Some(Err(e)) => {
+            error!("--eval script failed: {e:?}");
+            eprintln!("Error: --eval script failed: {e:?}");
+        },
+        None => {
+            eprintln!("Error: --eval callback was never called (timeout)");
+        },
+    }
+}
+
+/// Replace the document's contents with `html` via the `document.open()`/`write()`/
+/// `close()` reopen-the-document lifecycle, e.g. to seed a synthetic page or strip a
+/// cookie banner before capture.
+fn inject_html(servo: &Servo, event_loop: &ScraperEventLoop, webview: &WebView, html: &str) {
+    let escaped = serde_json::to_string(html).unwrap_or_else(|_| "\"\"".to_string());
+    let js = format!("document.open(); document.write({escaped}); document.close();");
+    match eval_js(servo, event_loop, webview, &js) {
+        Some(Ok(_)) => eprintln!("Injected {} bytes of HTML.", html.len()),
+        Some(Err(e)) => {
+            error!("--inject-html failed: {e:?}");
+            eprintln!("Error: --inject-html failed: {e:?}");
+        },
+        None => {
+            eprintln!("Error: --inject-html callback was never called (timeout)");
+        },
+    }
+}
+
+/// The `getBoundingClientRect()` shape used to resolve `--clip-selector`.
+#[derive(Debug, Clone, Copy, Deserialize)]
+struct ElementRect {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+/// Resolve the bounding box of the first element matching `selector`, via
+/// `getBoundingClientRect`. `None` if the selector matches nothing or the JS call
+/// fails.
+fn element_rect(
+    servo: &Servo,
+    event_loop: &ScraperEventLoop,
+    webview: &WebView,
+    selector: &str,
+) -> Option<(u32, u32, u32, u32)> {
+    let esc_sel = serde_json::to_string(selector).unwrap_or_else(|_| "\"\"".to_string());
+    let js = format!(
+        "(function() {{ \
+            var el = document.querySelector({esc_sel}); \
+            if (!el) return null; \
+            var r = el.getBoundingClientRect(); \
+            return JSON.stringify({{ x: r.x, y: r.y, width: r.width, height: r.height }}); \
+        }})()"
+    );
+    let rect: ElementRect = match eval_js(servo, event_loop, webview, &js) {
+        Some(Ok(JSValue::String(json))) => serde_json::from_str(&json).ok()?,
+        Some(Ok(JSValue::Null)) => {
+            eprintln!("Error: --clip-selector {selector:?} matched nothing");
+            return None;
+        },
+        Some(Ok(other)) => {
+            eprintln!("Error: unexpected JS result type for --clip-selector: {other:?}");
+            return None;
+        },
+        Some(Err(e)) => {
+            error!("--clip-selector lookup failed: {e:?}");
+            eprintln!("Error: --clip-selector lookup failed: {e:?}");
+            return None;
+        },
+        None => {
+            eprintln!("Error: --clip-selector callback was never called (timeout)");
+            return None;
+        },
+    };
+    Some((
+        rect.x.max(0.0) as u32,
+        rect.y.max(0.0) as u32,
+        rect.width.round() as u32,
+        rect.height.round() as u32,
+    ))
+}
+
 fn capture_html_to_file(
     servo: &Servo,
     event_loop: &ScraperEventLoop,
@@ -345,6 +725,79 @@ fn capture_html_to_file(
     }
 }
 
+/// Run `document.querySelectorAll(selector)` and collect outerHTML/textContent (and,
+/// if `attribute` is given, that attribute's value) for every match. `None` on a JS
+/// evaluation failure; `Some(vec![])` if the selector simply matched nothing.
+fn capture_selector(
+    servo: &Servo,
+    event_loop: &ScraperEventLoop,
+    webview: &WebView,
+    selector: &str,
+    attribute: Option<&str>,
+) -> Option<Vec<SelectorMatch>> {
+    let esc_sel = serde_json::to_string(selector).unwrap_or_else(|_| "\"\"".to_string());
+    let attr_expr = match attribute {
+        Some(attr) => {
+            let esc_attr = serde_json::to_string(attr).unwrap_or_else(|_| "\"\"".to_string());
+            format!("el.getAttribute({esc_attr})")
+        },
+        None => "null".to_string(),
+    };
+    let js = format!(
+        "JSON.stringify(Array.from(document.querySelectorAll({esc_sel})).map(function(el) {{ \
+            return {{ outer_html: el.outerHTML, text: el.textContent, attribute: {attr_expr} }}; \
+        }}))"
+    );
+    match eval_js(servo, event_loop, webview, &js) {
+        Some(Ok(JSValue::String(json))) => serde_json::from_str(&json).ok(),
+        Some(Ok(other)) => {
+            eprintln!("Error: unexpected JS result type for selector {selector:?}: {other:?}");
+            None
+        },
+        Some(Err(e)) => {
+            error!("JavaScript evaluation failed for selector {selector:?}: {e:?}");
+            eprintln!("Error: JavaScript evaluation failed for selector {selector:?}: {e:?}");
+            None
+        },
+        None => {
+            eprintln!("Error: JavaScript callback was never called (timeout) for selector {selector:?}");
+            None
+        },
+    }
+}
+
+fn save_console_to_file(messages: &[ConsoleMessage], path: &str) {
+    match serde_json::to_string_pretty(messages) {
+        Ok(json) => match std::fs::write(path, &json) {
+            Ok(()) => eprintln!("Console messages saved to {path} ({} entries)", messages.len()),
+            Err(e) => {
+                error!("Failed to write console messages to {path}: {e}");
+                eprintln!("Error: failed to write console messages: {e}");
+            },
+        },
+        Err(e) => {
+            error!("Failed to serialize console messages: {e}");
+            eprintln!("Error: failed to serialize console messages: {e}");
+        },
+    }
+}
+
+fn save_network_log_to_file(requests: &[NetworkRequest], path: &str) {
+    match serde_json::to_string_pretty(requests) {
+        Ok(json) => match std::fs::write(path, &json) {
+            Ok(()) => eprintln!("Network log saved to {path} ({} requests)", requests.len()),
+            Err(e) => {
+                error!("Failed to write network log to {path}: {e}");
+                eprintln!("Error: failed to write network log: {e}");
+            },
+        },
+        Err(e) => {
+            error!("Failed to serialize network log: {e}");
+            eprintln!("Error: failed to serialize network log: {e}");
+        },
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Main
 // ---------------------------------------------------------------------------
@@ -352,10 +805,40 @@ fn capture_html_to_file(
 fn main() {
     let config = scraper_config().run();
 
-    if config.screenshot.is_none() && config.html.is_none() {
-        eprintln!("Error: at least one of --screenshot or --html must be specified");
+    if config.screenshot.is_none()
+        && config.html.is_none()
+        && config.select.is_empty()
+        && config.eval.is_none()
+        && config.eval_file.is_none()
+    {
+        eprintln!(
+            "Error: at least one of --screenshot, --html, --select, --eval, or --eval-file must be specified"
+        );
+        process::exit(1);
+    }
+
+    // 0. Gather all URLs (positional, then --url-file/stdin) before starting Servo.
+    let mut url_strings = config.urls.clone();
+    if let Some(ref path) = config.url_file {
+        match read_url_list(path) {
+            Ok(mut more) => url_strings.append(&mut more),
+            Err(e) => {
+                eprintln!("Error: failed to read --url-file {path}: {e}");
+                process::exit(1);
+            },
+        }
+    }
+    if url_strings.is_empty() {
+        eprintln!("Error: no URLs given (pass at least one positional URL or --url-file)");
         process::exit(1);
     }
+    let urls: Vec<Url> = match url_strings.into_iter().map(parse_url).collect() {
+        Ok(urls) => urls,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        },
+    };
 
     // 1. Embedded resources — must be set before Servo reads them.
     resources::set(Box::new(EmbeddedResourceReader));
@@ -385,65 +868,184 @@ fn main() {
         .build();
     servo.setup_logging();
 
-    // 6. Create WebView with URL and delegate.
+    // 6. Create WebView with the first URL and delegate. The same Servo instance,
+    //    rendering context and WebView are reused for every URL in the batch below --
+    //    only `webview.load()` changes between iterations.
     let delegate = Rc::new(ScraperDelegate::default());
     let webview = WebViewBuilder::new(&servo, rendering_context.clone())
         .delegate(delegate.clone())
-        .url(config.url.clone())
+        .url(urls[0].clone())
         .build();
 
-    eprintln!("Loading {}...", config.url);
-
-    // 7. Wait for the page to finish loading, then let JS settle.
-    //    Suppress stderr during rendering to hide OpenGL diagnostics
-    //    ("UNSUPPORTED ... GLD_TEXTURE_INDEX_2D") which are harmless but noisy.
-    let d = delegate.clone();
-    with_stderr_suppressed(|| {
-        spin_until(
-            &servo,
-            &event_loop,
-            move || d.load_complete.get(),
-            config.timeout,
-        );
+    let mut require_selectors_failed = false;
+
+    for (index, url) in urls.iter().enumerate() {
+        if index > 0 {
+            delegate.load_complete.set(false);
+            if config.fullpage {
+                // Undo any full-page resize from the previous URL before navigating,
+                // so full-page detection below starts from the configured viewport.
+                webview.resize(PhysicalSize::new(config.width, config.height));
+            }
+            webview.load(url.clone());
+        }
+        eprintln!("Loading {url} ({}/{})...", index + 1, urls.len());
 
-        // 8. Let JS settle after load event (async scripts, requestAnimationFrame, etc.).
-        if config.wait > 0.0 {
-            spin_for(
+        // 7. Wait for the page to finish loading, then let JS settle.
+        //    Suppress stderr during rendering to hide OpenGL diagnostics
+        //    ("UNSUPPORTED ... GLD_TEXTURE_INDEX_2D") which are harmless but noisy.
+        let d = delegate.clone();
+        with_stderr_suppressed(|| {
+            spin_until(
                 &servo,
                 &event_loop,
-                Duration::from_secs_f64(config.wait),
+                || d.load_complete.get(),
+                config.timeout,
             );
+
+            // 8. Let JS settle after load event (async scripts, requestAnimationFrame, etc.).
+            if let Some(idle_ms) = config.wait_idle {
+                spin_until_network_idle(
+                    &servo,
+                    &event_loop,
+                    &d,
+                    Duration::from_millis(idle_ms),
+                    Duration::from_secs(config.timeout),
+                );
+            } else if config.wait > 0.0 {
+                spin_for(
+                    &servo,
+                    &event_loop,
+                    Duration::from_secs_f64(config.wait),
+                );
+            }
+        });
+        if let Some(idle_ms) = config.wait_idle {
+            eprintln!("Page settled after {idle_ms}ms of network idle.");
+        } else if config.wait > 0.0 {
+            eprintln!("Page loaded after {:.1}s settle time.", config.wait);
         }
-    });
-    if config.wait > 0.0 {
-        eprintln!("Page loaded after {:.1}s settle time.", config.wait);
-    }
-
-    // 9. For full-page screenshots, resize viewport to full document height.
-    if config.fullpage && config.screenshot.is_some() {
-        let js = "Math.max(document.documentElement.scrollHeight, document.body.scrollHeight)";
-        if let Some(Ok(JSValue::Number(doc_height))) =
-            eval_js(&servo, &event_loop, &webview, js)
-        {
-            let doc_height = doc_height as u32;
-            if doc_height > config.height {
-                eprintln!("Resizing viewport to {0}x{doc_height} for full-page capture...", config.width);
-                webview.resize(PhysicalSize::new(config.width, doc_height));
-                // Let the page re-layout and repaint at the new size.
-                spin_for(&servo, &event_loop, Duration::from_secs(1));
+
+        // 8b. Replace document contents and/or run user JavaScript before capture.
+        if let Some(ref path) = config.inject_html {
+            match std::fs::read_to_string(path) {
+                Ok(html) => inject_html(&servo, &event_loop, &webview, &html),
+                Err(e) => {
+                    error!("Failed to read --inject-html file {path}: {e}");
+                    eprintln!("Error: failed to read --inject-html file: {e}");
+                },
             }
         }
-    }
+        let eval_script = match config.eval_file {
+            Some(ref path) => match std::fs::read_to_string(path) {
+                Ok(script) => Some(script),
+                Err(e) => {
+                    error!("Failed to read --eval-file {path}: {e}");
+                    eprintln!("Error: failed to read --eval-file: {e}");
+                    None
+                },
+            },
+            None => config.eval.clone(),
+        };
+        if let Some(ref script) = eval_script {
+            run_eval_script(&servo, &event_loop, &webview, script);
+        }
 
-    // 10. Capture results.
-    if let Some(ref path) = config.screenshot {
-        take_screenshot_to_file(&servo, &event_loop, &webview, path);
-    }
-    if let Some(ref path) = config.html {
-        capture_html_to_file(&servo, &event_loop, &webview, path);
+        // 9. For full-page screenshots, resize viewport to full document height.
+        if config.fullpage && config.screenshot.is_some() {
+            let js = "Math.max(document.documentElement.scrollHeight, document.body.scrollHeight)";
+            if let Some(Ok(JSValue::Number(doc_height))) =
+                eval_js(&servo, &event_loop, &webview, js)
+            {
+                let doc_height = doc_height as u32;
+                if doc_height > config.height {
+                    eprintln!("Resizing viewport to {0}x{doc_height} for full-page capture...", config.width);
+                    webview.resize(PhysicalSize::new(config.width, doc_height));
+                    // Let the page re-layout and repaint at the new size.
+                    spin_for(&servo, &event_loop, Duration::from_secs(1));
+                }
+            }
+        }
+
+        // 10. Capture results. Output paths may contain `{index}` for multi-URL runs.
+        if let Some(ref path) = config.screenshot {
+            let path = apply_index_template(path, index);
+            let clip = if let Some(ref selector) = config.clip_selector {
+                element_rect(&servo, &event_loop, &webview, selector)
+            } else if let Some(ref spec) = config.clip {
+                match parse_clip_spec(spec) {
+                    Some(rect) => Some(rect),
+                    None => {
+                        eprintln!("Error: invalid --clip spec {spec:?}, expected X,Y,W,H");
+                        None
+                    },
+                }
+            } else {
+                None
+            };
+            take_screenshot_to_file(&servo, &event_loop, &webview, &path, clip, config.jpeg_quality);
+        }
+        if let Some(ref path) = config.html {
+            let path = apply_index_template(path, index);
+            capture_html_to_file(&servo, &event_loop, &webview, &path);
+        }
+        if let Some(ref path) = config.console {
+            let path = apply_index_template(path, index);
+            let messages: Vec<ConsoleMessage> =
+                delegate.console_messages.borrow_mut().drain(..).collect();
+            save_console_to_file(&messages, &path);
+        }
+        if let Some(ref path) = config.har {
+            let path = apply_index_template(path, index);
+            let requests: Vec<NetworkRequest> =
+                delegate.network_requests.borrow_mut().drain(..).collect();
+            save_network_log_to_file(&requests, &path);
+        }
+        if !config.select.is_empty() {
+            let mut results: HashMap<String, Vec<SelectorMatch>> = HashMap::new();
+            let mut empty_match = false;
+            for spec in &config.select {
+                let (selector, attribute) = parse_select_spec(spec);
+                if let Some(matches) =
+                    capture_selector(&servo, &event_loop, &webview, selector, attribute)
+                {
+                    if matches.is_empty() {
+                        eprintln!("Warning: selector {selector:?} matched nothing");
+                        empty_match = true;
+                    }
+                    results.insert(selector.to_string(), matches);
+                }
+            }
+            match serde_json::to_string_pretty(&results) {
+                Ok(json) => match config.select_output {
+                    Some(ref path) => {
+                        let path = apply_index_template(path, index);
+                        match std::fs::write(&path, &json) {
+                            Ok(()) => eprintln!("Selector results saved to {path}"),
+                            Err(e) => {
+                                error!("Failed to write selector results to {path}: {e}");
+                                eprintln!("Error: failed to write selector results: {e}");
+                            },
+                        }
+                    },
+                    None => println!("{json}"),
+                },
+                Err(e) => {
+                    error!("Failed to serialize selector results: {e}");
+                    eprintln!("Error: failed to serialize selector results: {e}");
+                },
+            }
+            if empty_match && config.require_selectors {
+                require_selectors_failed = true;
+            }
+        }
     }
 
     // 11. Cleanup is automatic via Drop on WebView and Servo.
     drop(webview);
     drop(servo);
+
+    if require_selectors_failed {
+        process::exit(1);
+    }
 }
@@ -4,14 +4,22 @@
 
 //! Layer 2: `Page` — thread-safe wrapper (`Send + Sync`).
 
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Mutex;
 use std::sync::mpsc;
 use std::thread;
 
+use regex::Regex;
+
 use crate::engine::PageEngine;
 use crate::types::{
-    ConsoleMessage, ElementRect, InputFile, NetworkRequest, PageError, PageOptions,
+    Action, Article, ConsoleMessage, Cookie, CoverageEntry, DeviceDescriptor, Dialog, DiffResult,
+    ElementHandle, ElementInfo, ElementRect, EmulationSettings, InputFile, InterceptedRequest,
+    JsException, Locator, MediaEmulation, NavigationError, NetworkRequest, PageError, PageEvent,
+    PageLifecycle, PageMetadata, PageOptions, PdfOptions, PointerButton, PopupEvent, PopupPolicy,
+    RequestDecision, ResponseBody, ScreenshotOptions,
 };
+use std::time::Duration;
 
 /// Commands sent from the `Page` handle to the background thread.
 enum Command {
@@ -23,27 +31,105 @@ enum Command {
         script: String,
         response: mpsc::Sender<Result<String, PageError>>,
     },
+    EvaluateIsolated {
+        script: String,
+        response: mpsc::Sender<Result<String, PageError>>,
+    },
     Screenshot {
         response: mpsc::Sender<Result<Vec<u8>, PageError>>,
     },
     ScreenshotFullpage {
         response: mpsc::Sender<Result<Vec<u8>, PageError>>,
     },
+    ScreenshotClip {
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        response: mpsc::Sender<Result<Vec<u8>, PageError>>,
+    },
+    ScreenshotElement {
+        selector: String,
+        response: mpsc::Sender<Result<Vec<u8>, PageError>>,
+    },
+    ScreenshotWith {
+        opts: ScreenshotOptions,
+        response: mpsc::Sender<Result<Vec<u8>, PageError>>,
+    },
+    ScreenshotDiff {
+        baseline: Vec<u8>,
+        response: mpsc::Sender<Result<DiffResult, PageError>>,
+    },
+    PrintToPdf {
+        opts: PdfOptions,
+        response: mpsc::Sender<Result<Vec<u8>, PageError>>,
+    },
     Html {
         response: mpsc::Sender<Result<String, PageError>>,
     },
+    HtmlStatic {
+        response: mpsc::Sender<Result<String, PageError>>,
+    },
     Url {
         response: mpsc::Sender<Option<String>>,
     },
     Title {
         response: mpsc::Sender<Option<String>>,
     },
+    PageMetadata {
+        response: mpsc::Sender<Result<PageMetadata, PageError>>,
+    },
+    PageMarkdown {
+        selector: Option<String>,
+        response: mpsc::Sender<Result<String, PageError>>,
+    },
+    Links {
+        response: mpsc::Sender<Result<Vec<String>, PageError>>,
+    },
     ConsoleMessages {
         response: mpsc::Sender<Vec<ConsoleMessage>>,
     },
+    JsExceptions {
+        response: mpsc::Sender<Vec<JsException>>,
+    },
+    LastNavigationError {
+        response: mpsc::Sender<Option<NavigationError>>,
+    },
+    StartJsCoverage {
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
+    StopJsCoverage {
+        response: mpsc::Sender<Result<Vec<CoverageEntry>, PageError>>,
+    },
+    StartCssCoverage {
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
+    StopCssCoverage {
+        response: mpsc::Sender<Result<Vec<CoverageEntry>, PageError>>,
+    },
+    DialogMessages {
+        response: mpsc::Sender<Vec<Dialog>>,
+    },
     NetworkRequests {
         response: mpsc::Sender<Vec<NetworkRequest>>,
     },
+    NetworkResponses {
+        response: mpsc::Sender<Vec<NetworkRequest>>,
+    },
+    GetResponseBody {
+        url: String,
+        response: mpsc::Sender<Option<Vec<u8>>>,
+    },
+    ResponseBody {
+        request_id: String,
+        response: mpsc::Sender<Result<ResponseBody, PageError>>,
+    },
+    InterceptedRequests {
+        response: mpsc::Sender<Vec<InterceptedRequest>>,
+    },
+    Har {
+        response: mpsc::Sender<Result<String, PageError>>,
+    },
     Close {
         response: mpsc::Sender<()>,
     },
@@ -56,6 +142,11 @@ enum Command {
         timeout: u64,
         response: mpsc::Sender<Result<(), PageError>>,
     },
+    WaitForSelectorGone {
+        selector: String,
+        timeout: u64,
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
     WaitForCondition {
         js_expr: String,
         timeout: u64,
@@ -84,6 +175,10 @@ enum Command {
         selector: String,
         response: mpsc::Sender<Result<(), PageError>>,
     },
+    Focus {
+        selector: String,
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
     TypeText {
         text: String,
         response: mpsc::Sender<Result<(), PageError>>,
@@ -123,14 +218,83 @@ enum Command {
     GetCookies {
         response: mpsc::Sender<Result<String, PageError>>,
     },
-    SetCookie {
+    SetCookieRaw {
         cookie: String,
         response: mpsc::Sender<Result<(), PageError>>,
     },
     ClearCookies {
         response: mpsc::Sender<Result<(), PageError>>,
     },
+    Cookies {
+        response: mpsc::Sender<Result<Vec<Cookie>, PageError>>,
+    },
+    SetCookie {
+        cookie: Cookie,
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
+    SetCookies {
+        cookies: Vec<Cookie>,
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
+    AddInitScript {
+        script: String,
+        response: mpsc::Sender<u32>,
+    },
+    RemoveInitScript {
+        id: u32,
+        response: mpsc::Sender<bool>,
+    },
+    ExposeFunction {
+        name: String,
+        handler: Box<dyn FnMut(String) -> Option<String> + Send + 'static>,
+        response: mpsc::Sender<()>,
+    },
+    SetUserAgent {
+        user_agent: String,
+        response: mpsc::Sender<()>,
+    },
+    SetHttpAuth {
+        username: String,
+        password: String,
+        response: mpsc::Sender<()>,
+    },
+    OnAuthRequired {
+        callback: Box<dyn FnMut(&str) -> Option<(String, String)> + Send + 'static>,
+        response: mpsc::Sender<()>,
+    },
+    SetNavigatorOverride {
+        field: String,
+        value: String,
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
+    SetViewport {
+        width: u32,
+        height: u32,
+        device_scale: f32,
+        response: mpsc::Sender<()>,
+    },
+    SetExtraHttpHeaders {
+        headers: HashMap<String, String>,
+        response: mpsc::Sender<()>,
+    },
+    SetEmulation {
+        settings: EmulationSettings,
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
+    Emulate {
+        device: DeviceDescriptor,
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
+    EmulateMedia {
+        media: MediaEmulation,
+        response: mpsc::Sender<()>,
+    },
     // Request interception
+    InterceptRequests {
+        pattern: String,
+        handler: Box<dyn FnMut(&NetworkRequest) -> RequestDecision + Send + 'static>,
+        response: mpsc::Sender<()>,
+    },
     BlockUrls {
         patterns: Vec<String>,
         response: mpsc::Sender<()>,
@@ -138,6 +302,13 @@ enum Command {
     ClearBlockedUrls {
         response: mpsc::Sender<()>,
     },
+    AddRoute {
+        rule_json: String,
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
+    ClearRoutes {
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
     // Navigation
     Reload {
         response: mpsc::Sender<Result<(), PageError>>,
@@ -166,6 +337,150 @@ enum Command {
         selector: String,
         response: mpsc::Sender<Result<String, PageError>>,
     },
+    ElementRectBy {
+        locator: Locator,
+        response: mpsc::Sender<Result<ElementRect, PageError>>,
+    },
+    ElementTextBy {
+        locator: Locator,
+        response: mpsc::Sender<Result<String, PageError>>,
+    },
+    ElementAttributeBy {
+        locator: Locator,
+        attribute: String,
+        response: mpsc::Sender<Result<Option<String>, PageError>>,
+    },
+    ElementHtmlBy {
+        locator: Locator,
+        response: mpsc::Sender<Result<String, PageError>>,
+    },
+    ElementsRect {
+        selector: String,
+        response: mpsc::Sender<Result<String, PageError>>,
+    },
+    ElementsText {
+        selector: String,
+        response: mpsc::Sender<Result<String, PageError>>,
+    },
+    ElementsAttribute {
+        selector: String,
+        attribute: String,
+        response: mpsc::Sender<Result<String, PageError>>,
+    },
+    ElementsHtml {
+        selector: String,
+        response: mpsc::Sender<Result<String, PageError>>,
+    },
+    QueryAll {
+        selector: String,
+        response: mpsc::Sender<Result<Vec<ElementInfo>, PageError>>,
+    },
+    ElementInfo {
+        selector: String,
+        response: mpsc::Sender<Result<ElementInfo, PageError>>,
+    },
+    Find {
+        selector: String,
+        response: mpsc::Sender<Result<Option<ElementHandle>, PageError>>,
+    },
+    FindAll {
+        selector: String,
+        response: mpsc::Sender<Result<Vec<ElementHandle>, PageError>>,
+    },
+    HandleText {
+        handle: ElementHandle,
+        response: mpsc::Sender<Result<String, PageError>>,
+    },
+    HandleAttribute {
+        handle: ElementHandle,
+        attribute: String,
+        response: mpsc::Sender<Result<Option<String>, PageError>>,
+    },
+    HandleBoundingBox {
+        handle: ElementHandle,
+        response: mpsc::Sender<Result<ElementRect, PageError>>,
+    },
+    HandleClick {
+        handle: ElementHandle,
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
+    HandleTypeText {
+        handle: ElementHandle,
+        text: String,
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
+    Snapshot {
+        spec_json: String,
+        response: mpsc::Sender<Result<String, PageError>>,
+    },
+    SaveArchive {
+        flags: u32,
+        response: mpsc::Sender<Result<String, PageError>>,
+    },
+    ExtractArticle {
+        response: mpsc::Sender<Result<Article, PageError>>,
+    },
+    SaveEpub {
+        dest_path: String,
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
+    PerformActions {
+        json: String,
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
+    PerformActionSequence {
+        actions: Vec<Action>,
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
+    TypeTextSelector {
+        selector: String,
+        text: String,
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
+    GetCookiesJson {
+        response: mpsc::Sender<Result<String, PageError>>,
+    },
+    SetCookieStruct {
+        json: String,
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
+    DeleteCookie {
+        name: String,
+        domain: Option<String>,
+        path: Option<String>,
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
+    FindText {
+        query: String,
+        flags: u32,
+        response: mpsc::Sender<Result<u32, PageError>>,
+    },
+    FindNext {
+        response: mpsc::Sender<Result<ElementRect, PageError>>,
+    },
+    FindPrevious {
+        response: mpsc::Sender<Result<ElementRect, PageError>>,
+    },
+    FindClear {
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
+    SetDownloadCapture {
+        enabled: bool,
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
+    WaitForDownloads {
+        count: usize,
+        timeout: u64,
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
+    GetDownloads {
+        response: mpsc::Sender<Result<String, PageError>>,
+    },
+    SaveDownload {
+        index: u32,
+        dest_path: String,
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
     // Multi-page
     NewPage {
         response: mpsc::Sender<Result<u32, PageError>>,
@@ -183,6 +498,14 @@ enum Command {
         page_id: u32,
         response: mpsc::Sender<Result<(), PageError>>,
     },
+    DiscardPage {
+        page_id: u32,
+        response: mpsc::Sender<Result<(), PageError>>,
+    },
+    PageState {
+        page_id: u32,
+        response: mpsc::Sender<Result<PageLifecycle, PageError>>,
+    },
     ActivePageId {
         response: mpsc::Sender<Option<u32>>,
     },
@@ -196,6 +519,17 @@ enum Command {
         enabled: bool,
         response: mpsc::Sender<()>,
     },
+    SetPopupPolicy {
+        policy: PopupPolicy,
+        response: mpsc::Sender<()>,
+    },
+    PopupEvents {
+        response: mpsc::Sender<mpsc::Receiver<PopupEvent>>,
+    },
+    CaptureResponseBodies {
+        enabled: bool,
+        response: mpsc::Sender<()>,
+    },
     PopupPages {
         response: mpsc::Sender<Vec<u32>>,
     },
@@ -207,6 +541,18 @@ enum Command {
         page_id: u32,
         response: mpsc::Sender<Option<String>>,
     },
+    PageToPdf {
+        page_id: u32,
+        opts: PdfOptions,
+        response: mpsc::Sender<Result<Vec<u8>, PageError>>,
+    },
+    Subscribe {
+        kinds: u32,
+        response: mpsc::Sender<mpsc::Receiver<PageEvent>>,
+    },
+    Unsubscribe {
+        response: mpsc::Sender<()>,
+    },
     Shutdown,
 }
 
@@ -217,6 +563,9 @@ enum Command {
 pub struct Page {
     sender: Mutex<mpsc::Sender<Command>>,
     thread: Mutex<Option<thread::JoinHandle<()>>>,
+    /// Applied to every call via [`Self::send_cmd`] unless overridden per-call.
+    /// `None` (the default) blocks indefinitely, matching prior behavior.
+    default_timeout: Mutex<Option<Duration>>,
 }
 
 unsafe impl Send for Page {}
@@ -248,27 +597,102 @@ impl Page {
                     Command::Evaluate { script, response } => {
                         let _ = response.send(engine.evaluate(&script));
                     }
+                    Command::EvaluateIsolated { script, response } => {
+                        let _ = response.send(engine.evaluate_isolated(&script));
+                    }
                     Command::Screenshot { response } => {
                         let _ = response.send(engine.screenshot());
                     }
                     Command::ScreenshotFullpage { response } => {
                         let _ = response.send(engine.screenshot_fullpage());
                     }
+                    Command::ScreenshotClip {
+                        x,
+                        y,
+                        width,
+                        height,
+                        response,
+                    } => {
+                        let _ = response.send(engine.screenshot_clip(x, y, width, height));
+                    }
+                    Command::ScreenshotElement { selector, response } => {
+                        let _ = response.send(engine.screenshot_element(&selector));
+                    }
+                    Command::ScreenshotWith { opts, response } => {
+                        let _ = response.send(engine.screenshot_with(opts));
+                    }
+                    Command::ScreenshotDiff { baseline, response } => {
+                        let _ = response.send(engine.screenshot_diff(&baseline));
+                    }
+                    Command::PrintToPdf { opts, response } => {
+                        let _ = response.send(engine.print_to_pdf(opts));
+                    }
                     Command::Html { response } => {
                         let _ = response.send(engine.html());
                     }
+                    Command::HtmlStatic { response } => {
+                        let _ = response.send(engine.html_static());
+                    }
                     Command::Url { response } => {
                         let _ = response.send(engine.url());
                     }
                     Command::Title { response } => {
                         let _ = response.send(engine.title());
                     }
+                    Command::PageMetadata { response } => {
+                        let _ = response.send(engine.metadata());
+                    }
+                    Command::PageMarkdown { selector, response } => {
+                        let _ = response.send(engine.markdown(selector.as_deref()));
+                    }
+                    Command::Links { response } => {
+                        let _ = response.send(engine.links());
+                    }
                     Command::ConsoleMessages { response } => {
                         let _ = response.send(engine.console_messages());
                     }
+                    Command::JsExceptions { response } => {
+                        let _ = response.send(engine.js_exceptions());
+                    }
+                    Command::LastNavigationError { response } => {
+                        let _ = response.send(engine.last_navigation_error());
+                    }
+                    Command::StartJsCoverage { response } => {
+                        let _ = response.send(engine.start_js_coverage());
+                    }
+                    Command::StopJsCoverage { response } => {
+                        let _ = response.send(engine.stop_js_coverage());
+                    }
+                    Command::StartCssCoverage { response } => {
+                        let _ = response.send(engine.start_css_coverage());
+                    }
+                    Command::StopCssCoverage { response } => {
+                        let _ = response.send(engine.stop_css_coverage());
+                    }
+                    Command::DialogMessages { response } => {
+                        let _ = response.send(engine.dialog_messages());
+                    }
                     Command::NetworkRequests { response } => {
                         let _ = response.send(engine.network_requests());
                     }
+                    Command::NetworkResponses { response } => {
+                        let _ = response.send(engine.network_responses());
+                    }
+                    Command::GetResponseBody { url, response } => {
+                        let _ = response.send(engine.get_response_body(&url));
+                    }
+                    Command::ResponseBody {
+                        request_id,
+                        response,
+                    } => {
+                        let _ = response.send(engine.response_body(&request_id));
+                    }
+                    Command::InterceptedRequests { response } => {
+                        let _ = response.send(engine.intercepted_requests());
+                    }
+                    Command::Har { response } => {
+                        let _ = response.send(engine.har());
+                    }
                     Command::Close { response } => {
                         engine.close();
                         let _ = response.send(());
@@ -284,6 +708,13 @@ impl Page {
                     } => {
                         let _ = response.send(engine.wait_for_selector(&selector, timeout));
                     }
+                    Command::WaitForSelectorGone {
+                        selector,
+                        timeout,
+                        response,
+                    } => {
+                        let _ = response.send(engine.wait_for_selector_gone(&selector, timeout));
+                    }
                     Command::WaitForCondition {
                         js_expr,
                         timeout,
@@ -311,6 +742,9 @@ impl Page {
                     Command::ClickSelector { selector, response } => {
                         let _ = response.send(engine.click_selector(&selector));
                     }
+                    Command::Focus { selector, response } => {
+                        let _ = response.send(engine.focus(&selector));
+                    }
                     Command::TypeText { text, response } => {
                         let _ = response.send(engine.type_text(&text));
                     }
@@ -347,55 +781,291 @@ impl Page {
                     Command::GetCookies { response } => {
                         let _ = response.send(engine.get_cookies());
                     }
-                    Command::SetCookie { cookie, response } => {
-                        let _ = response.send(engine.set_cookie(&cookie));
+                    Command::SetCookieRaw { cookie, response } => {
+                        let _ = response.send(engine.set_cookie_raw(&cookie));
                     }
                     Command::ClearCookies { response } => {
                         let _ = response.send(engine.clear_cookies());
                     }
-                    Command::BlockUrls { patterns, response } => {
-                        engine.block_urls(patterns);
-                        let _ = response.send(());
-                    }
-                    Command::ClearBlockedUrls { response } => {
-                        engine.clear_blocked_urls();
-                        let _ = response.send(());
-                    }
-                    Command::Reload { response } => {
-                        let _ = response.send(engine.reload());
+                    Command::Cookies { response } => {
+                        let _ = response.send(engine.cookies());
                     }
-                    Command::GoBack { response } => {
-                        let _ = response.send(engine.go_back());
+                    Command::SetCookie { cookie, response } => {
+                        let _ = response.send(engine.set_cookie(&cookie));
                     }
-                    Command::GoForward { response } => {
-                        let _ = response.send(engine.go_forward());
+                    Command::SetCookies { cookies, response } => {
+                        let _ = response.send(engine.set_cookies(&cookies));
                     }
-                    Command::ElementRect { selector, response } => {
-                        let _ = response.send(engine.element_rect(&selector));
+                    Command::AddInitScript { script, response } => {
+                        let id = engine.add_init_script(script);
+                        let _ = response.send(id);
                     }
-                    Command::ElementText { selector, response } => {
-                        let _ = response.send(engine.element_text(&selector));
+                    Command::RemoveInitScript { id, response } => {
+                        let _ = response.send(engine.remove_init_script(id));
                     }
-                    Command::ElementAttribute {
-                        selector,
-                        attribute,
+                    Command::ExposeFunction {
+                        name,
+                        mut handler,
                         response,
                     } => {
-                        let _ = response.send(engine.element_attribute(&selector, &attribute));
-                    }
-                    Command::ElementHtml { selector, response } => {
-                        let _ = response.send(engine.element_html(&selector));
-                    }
-                    // Multi-page
-                    Command::NewPage { response } => {
-                        let _ = response.send(engine.new_page());
+                        engine.expose_function(name, move |payload| handler(payload));
+                        let _ = response.send(());
                     }
-                    Command::NewPageWithSize {
-                        width,
-                        height,
+                    Command::SetUserAgent {
+                        user_agent,
                         response,
                     } => {
-                        let _ = response.send(engine.new_page_with_size(width, height));
+                        engine.set_user_agent(user_agent);
+                        let _ = response.send(());
+                    }
+                    Command::SetHttpAuth {
+                        username,
+                        password,
+                        response,
+                    } => {
+                        engine.set_http_auth(username, password);
+                        let _ = response.send(());
+                    }
+                    Command::OnAuthRequired {
+                        mut callback,
+                        response,
+                    } => {
+                        engine.on_auth_required(move |origin| callback(origin));
+                        let _ = response.send(());
+                    }
+                    Command::SetNavigatorOverride {
+                        field,
+                        value,
+                        response,
+                    } => {
+                        let _ = response.send(engine.set_navigator_override(&field, &value));
+                    }
+                    Command::SetViewport {
+                        width,
+                        height,
+                        device_scale,
+                        response,
+                    } => {
+                        engine.set_viewport(width, height, device_scale);
+                        let _ = response.send(());
+                    }
+                    Command::SetEmulation { settings, response } => {
+                        let _ = response.send(engine.set_emulation(settings));
+                    }
+                    Command::Emulate { device, response } => {
+                        let _ = response.send(engine.emulate(&device));
+                    }
+                    Command::EmulateMedia { media, response } => {
+                        engine.emulate_media(media);
+                        let _ = response.send(());
+                    }
+                    Command::SetExtraHttpHeaders { headers, response } => {
+                        engine.set_extra_http_headers(headers);
+                        let _ = response.send(());
+                    }
+                    Command::InterceptRequests {
+                        pattern,
+                        mut handler,
+                        response,
+                    } => {
+                        engine.intercept_requests(&pattern, move |req| handler(req));
+                        let _ = response.send(());
+                    }
+                    Command::BlockUrls { patterns, response } => {
+                        engine.block_urls(patterns);
+                        let _ = response.send(());
+                    }
+                    Command::ClearBlockedUrls { response } => {
+                        engine.clear_blocked_urls();
+                        let _ = response.send(());
+                    }
+                    Command::AddRoute { rule_json, response } => {
+                        let _ = response.send(engine.add_route(&rule_json));
+                    }
+                    Command::ClearRoutes { response } => {
+                        let _ = response.send(engine.clear_routes());
+                    }
+                    Command::Reload { response } => {
+                        let _ = response.send(engine.reload());
+                    }
+                    Command::GoBack { response } => {
+                        let _ = response.send(engine.go_back());
+                    }
+                    Command::GoForward { response } => {
+                        let _ = response.send(engine.go_forward());
+                    }
+                    Command::ElementRect { selector, response } => {
+                        let _ = response.send(engine.element_rect(&selector));
+                    }
+                    Command::ElementText { selector, response } => {
+                        let _ = response.send(engine.element_text(&selector));
+                    }
+                    Command::ElementAttribute {
+                        selector,
+                        attribute,
+                        response,
+                    } => {
+                        let _ = response.send(engine.element_attribute(&selector, &attribute));
+                    }
+                    Command::ElementHtml { selector, response } => {
+                        let _ = response.send(engine.element_html(&selector));
+                    }
+                    Command::ElementRectBy { locator, response } => {
+                        let _ = response.send(engine.element_rect_by(&locator));
+                    }
+                    Command::ElementTextBy { locator, response } => {
+                        let _ = response.send(engine.element_text_by(&locator));
+                    }
+                    Command::ElementAttributeBy {
+                        locator,
+                        attribute,
+                        response,
+                    } => {
+                        let _ = response.send(engine.element_attribute_by(&locator, &attribute));
+                    }
+                    Command::ElementHtmlBy { locator, response } => {
+                        let _ = response.send(engine.element_html_by(&locator));
+                    }
+                    Command::ElementsRect { selector, response } => {
+                        let _ = response.send(engine.elements_rect(&selector));
+                    }
+                    Command::ElementsText { selector, response } => {
+                        let _ = response.send(engine.elements_text(&selector));
+                    }
+                    Command::ElementsAttribute {
+                        selector,
+                        attribute,
+                        response,
+                    } => {
+                        let _ = response.send(engine.elements_attribute(&selector, &attribute));
+                    }
+                    Command::ElementsHtml { selector, response } => {
+                        let _ = response.send(engine.elements_html(&selector));
+                    }
+                    Command::QueryAll { selector, response } => {
+                        let _ = response.send(engine.query_all(&selector));
+                    }
+                    Command::ElementInfo { selector, response } => {
+                        let _ = response.send(engine.element_info(&selector));
+                    }
+                    Command::Find { selector, response } => {
+                        let _ = response.send(engine.find(&selector));
+                    }
+                    Command::FindAll { selector, response } => {
+                        let _ = response.send(engine.find_all(&selector));
+                    }
+                    Command::HandleText { handle, response } => {
+                        let _ = response.send(engine.handle_text(&handle));
+                    }
+                    Command::HandleAttribute {
+                        handle,
+                        attribute,
+                        response,
+                    } => {
+                        let _ = response.send(engine.handle_attribute(&handle, &attribute));
+                    }
+                    Command::HandleBoundingBox { handle, response } => {
+                        let _ = response.send(engine.handle_bounding_box(&handle));
+                    }
+                    Command::HandleClick { handle, response } => {
+                        let _ = response.send(engine.handle_click(&handle));
+                    }
+                    Command::HandleTypeText {
+                        handle,
+                        text,
+                        response,
+                    } => {
+                        let _ = response.send(engine.handle_type_text(&handle, &text));
+                    }
+                    Command::Snapshot { spec_json, response } => {
+                        let _ = response.send(engine.snapshot(&spec_json));
+                    }
+                    Command::SaveArchive { flags, response } => {
+                        let _ = response.send(engine.save_archive(flags));
+                    }
+                    Command::ExtractArticle { response } => {
+                        let _ = response.send(engine.extract_article());
+                    }
+                    Command::SaveEpub { dest_path, response } => {
+                        let _ = response.send(engine.save_epub(&dest_path));
+                    }
+                    Command::PerformActions { json, response } => {
+                        let _ = response.send(engine.perform_actions(&json));
+                    }
+                    Command::PerformActionSequence { actions, response } => {
+                        let _ = response.send(engine.perform_action_sequence(actions));
+                    }
+                    Command::TypeTextSelector {
+                        selector,
+                        text,
+                        response,
+                    } => {
+                        let _ = response.send(engine.type_text_selector(&selector, &text));
+                    }
+                    Command::GetCookiesJson { response } => {
+                        let _ = response.send(engine.get_cookies_json());
+                    }
+                    Command::SetCookieStruct { json, response } => {
+                        let _ = response.send(engine.set_cookie_struct(&json));
+                    }
+                    Command::DeleteCookie {
+                        name,
+                        domain,
+                        path,
+                        response,
+                    } => {
+                        let _ = response.send(engine.delete_cookie(
+                            &name,
+                            domain.as_deref(),
+                            path.as_deref(),
+                        ));
+                    }
+                    Command::FindText {
+                        query,
+                        flags,
+                        response,
+                    } => {
+                        let _ = response.send(engine.find_text(&query, flags));
+                    }
+                    Command::FindNext { response } => {
+                        let _ = response.send(engine.find_next());
+                    }
+                    Command::FindPrevious { response } => {
+                        let _ = response.send(engine.find_previous());
+                    }
+                    Command::FindClear { response } => {
+                        let _ = response.send(engine.find_clear());
+                    }
+                    Command::SetDownloadCapture { enabled, response } => {
+                        let _ = response.send(engine.set_download_capture(enabled));
+                    }
+                    Command::WaitForDownloads {
+                        count,
+                        timeout,
+                        response,
+                    } => {
+                        let _ = response.send(engine.wait_for_downloads(count, timeout));
+                    }
+                    Command::GetDownloads { response } => {
+                        let _ = response.send(engine.get_downloads());
+                    }
+                    Command::SaveDownload {
+                        index,
+                        dest_path,
+                        response,
+                    } => {
+                        let _ = response.send(engine.save_download(index, &dest_path));
+                    }
+                    // Multi-page
+                    Command::NewPage { response } => {
+                        let _ = response.send(engine.new_page());
+                    }
+                    Command::NewPageWithSize {
+                        width,
+                        height,
+                        response,
+                    } => {
+                        let _ = response.send(engine.new_page_with_size(width, height));
                     }
                     Command::SwitchTo { page_id, response } => {
                         let _ = response.send(engine.switch_to(page_id));
@@ -403,6 +1073,12 @@ impl Page {
                     Command::ClosePage { page_id, response } => {
                         let _ = response.send(engine.close_page(page_id));
                     }
+                    Command::DiscardPage { page_id, response } => {
+                        let _ = response.send(engine.discard_page(page_id));
+                    }
+                    Command::PageState { page_id, response } => {
+                        let _ = response.send(engine.page_state(page_id));
+                    }
                     Command::ActivePageId { response } => {
                         let _ = response.send(engine.active_page_id());
                     }
@@ -416,6 +1092,17 @@ impl Page {
                         engine.set_popup_handling(enabled);
                         let _ = response.send(());
                     }
+                    Command::SetPopupPolicy { policy, response } => {
+                        engine.set_popup_policy(policy);
+                        let _ = response.send(());
+                    }
+                    Command::PopupEvents { response } => {
+                        let _ = response.send(engine.popup_events());
+                    }
+                    Command::CaptureResponseBodies { enabled, response } => {
+                        engine.capture_response_bodies(enabled);
+                        let _ = response.send(());
+                    }
                     Command::PopupPages { response } => {
                         let _ = response.send(engine.popup_pages());
                     }
@@ -425,6 +1112,20 @@ impl Page {
                     Command::PageTitle { page_id, response } => {
                         let _ = response.send(engine.page_title(page_id));
                     }
+                    Command::PageToPdf {
+                        page_id,
+                        opts,
+                        response,
+                    } => {
+                        let _ = response.send(engine.page_to_pdf(page_id, opts));
+                    }
+                    Command::Subscribe { kinds, response } => {
+                        let _ = response.send(engine.subscribe(kinds));
+                    }
+                    Command::Unsubscribe { response } => {
+                        engine.unsubscribe();
+                        let _ = response.send(());
+                    }
                     Command::Shutdown => break,
                 }
             }
@@ -437,12 +1138,45 @@ impl Page {
         Ok(Self {
             sender: Mutex::new(cmd_tx),
             thread: Mutex::new(Some(thread)),
+            default_timeout: Mutex::new(None),
         })
     }
 
+    /// Builder-style: bound every call that doesn't specify its own timeout (e.g.
+    /// [`Self::evaluate_with_timeout`]) to `timeout`, so a stalled navigation or a JS
+    /// `evaluate` that never returns can't wedge the calling thread forever.
+    /// `Page::new(opts)?.with_timeout(Duration::from_secs(10))`.
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        self.set_default_timeout(Some(timeout));
+        self
+    }
+
+    /// Change the default timeout after construction. `None` restores the prior
+    /// behavior of blocking indefinitely. Useful for FFI callers that only learn the
+    /// desired bound after the page handle already exists.
+    pub fn set_default_timeout(&self, timeout: Option<Duration>) {
+        *self.default_timeout.lock().unwrap() = timeout;
+    }
+
     fn send_cmd<T>(
         &self,
         make_cmd: impl FnOnce(mpsc::Sender<T>) -> Command,
+    ) -> Result<T, PageError> {
+        self.send_cmd_timeout(make_cmd, None)
+    }
+
+    /// Like [`Self::send_cmd`], but `timeout` (falling back to the page's default set
+    /// via [`Self::with_timeout`]/[`Self::set_default_timeout`]) bounds the wait for a
+    /// reply, returning [`PageError::Timeout`] if it elapses. The background thread
+    /// keeps running the orphaned command and will still try to send its reply, but
+    /// each call gets its own dedicated one-shot channel here (unlike a shared
+    /// request/response stream), so that reply simply has nowhere to go once we've
+    /// stopped listening -- there's no risk of it being mismatched with a later
+    /// caller's response, and so no correlation id is needed to discard it.
+    fn send_cmd_timeout<T>(
+        &self,
+        make_cmd: impl FnOnce(mpsc::Sender<T>) -> Command,
+        timeout: Option<Duration>,
     ) -> Result<T, PageError> {
         let (resp_tx, resp_rx) = mpsc::channel();
         let sender = self.sender.lock().map_err(|_| PageError::ChannelClosed)?;
@@ -450,7 +1184,15 @@ impl Page {
             .send(make_cmd(resp_tx))
             .map_err(|_| PageError::ChannelClosed)?;
         drop(sender);
-        resp_rx.recv().map_err(|_| PageError::ChannelClosed)
+
+        let timeout = timeout.or_else(|| *self.default_timeout.lock().unwrap());
+        match timeout {
+            Some(timeout) => resp_rx.recv_timeout(timeout).map_err(|e| match e {
+                mpsc::RecvTimeoutError::Timeout => PageError::Timeout,
+                mpsc::RecvTimeoutError::Disconnected => PageError::ChannelClosed,
+            }),
+            None => resp_rx.recv().map_err(|_| PageError::ChannelClosed),
+        }
     }
 
     pub fn open(&self, url: &str) -> Result<(), PageError> {
@@ -467,6 +1209,27 @@ impl Page {
         })?
     }
 
+    /// Like [`Self::evaluate`], but bounds the wait for a reply to `timeout` regardless
+    /// of the page's default (see [`Self::with_timeout`]) -- useful for a one-off
+    /// script that might hang without lowering the timeout for every other call.
+    pub fn evaluate_with_timeout(&self, script: &str, timeout: Duration) -> Result<String, PageError> {
+        self.send_cmd_timeout(
+            |response| Command::Evaluate {
+                script: script.to_string(),
+                response,
+            },
+            Some(timeout),
+        )?
+    }
+
+    /// See [`crate::engine::PageEngine::evaluate_isolated`].
+    pub fn evaluate_isolated(&self, script: &str) -> Result<String, PageError> {
+        self.send_cmd(|response| Command::EvaluateIsolated {
+            script: script.to_string(),
+            response,
+        })?
+    }
+
     pub fn screenshot(&self) -> Result<Vec<u8>, PageError> {
         self.send_cmd(|response| Command::Screenshot { response })?
     }
@@ -475,10 +1238,64 @@ impl Page {
         self.send_cmd(|response| Command::ScreenshotFullpage { response })?
     }
 
+    /// Screenshot of the viewport cropped to `(x, y, width, height)`. See
+    /// [`crate::engine::PageEngine::screenshot_clip`].
+    pub fn screenshot_clip(
+        &self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> Result<Vec<u8>, PageError> {
+        self.send_cmd(|response| Command::ScreenshotClip {
+            x,
+            y,
+            width,
+            height,
+            response,
+        })?
+    }
+
+    /// Screenshot cropped to the bounding rect of the first element matching
+    /// `selector`. See [`crate::engine::PageEngine::screenshot_element`].
+    pub fn screenshot_element(&self, selector: &str) -> Result<Vec<u8>, PageError> {
+        self.send_cmd(|response| Command::ScreenshotElement {
+            selector: selector.to_string(),
+            response,
+        })?
+    }
+
+    /// Screenshot with full control over format, clip region, and background
+    /// handling. See [`crate::engine::PageEngine::screenshot_with`].
+    pub fn screenshot_with(&self, opts: ScreenshotOptions) -> Result<Vec<u8>, PageError> {
+        self.send_cmd(|response| Command::ScreenshotWith { opts, response })?
+    }
+
+    /// Capture the current viewport and diff it against `baseline`. See
+    /// [`crate::engine::PageEngine::screenshot_diff`].
+    pub fn screenshot_diff(&self, baseline: &[u8]) -> Result<DiffResult, PageError> {
+        self.send_cmd(|response| Command::ScreenshotDiff {
+            baseline: baseline.to_vec(),
+            response,
+        })?
+    }
+
+    /// Render the current viewport to a PDF. See
+    /// [`crate::engine::PageEngine::print_to_pdf`].
+    pub fn print_to_pdf(&self, opts: PdfOptions) -> Result<Vec<u8>, PageError> {
+        self.send_cmd(|response| Command::PrintToPdf { opts, response })?
+    }
+
     pub fn html(&self) -> Result<String, PageError> {
         self.send_cmd(|response| Command::Html { response })?
     }
 
+    /// Capture the page's HTML with `<noscript>` content expanded in place and all
+    /// scripting stripped. See [`crate::engine::PageEngine::html_static`].
+    pub fn html_static(&self) -> Result<String, PageError> {
+        self.send_cmd(|response| Command::HtmlStatic { response })?
+    }
+
     pub fn url(&self) -> Option<String> {
         self.send_cmd(|response| Command::Url { response })
             .ok()
@@ -491,16 +1308,120 @@ impl Page {
             .flatten()
     }
 
+    /// Gather Open Graph/Twitter Card/canonical-link/`ld+json` metadata for the active
+    /// page. See [`crate::engine::PageEngine::metadata`].
+    pub fn metadata(&self) -> Result<PageMetadata, PageError> {
+        self.send_cmd(|response| Command::PageMetadata { response })?
+    }
+
+    /// Render the page (or the subtree rooted at `selector`, if given) as
+    /// Markdown. See [`crate::engine::PageEngine::markdown`].
+    pub fn markdown(&self, selector: Option<&str>) -> Result<String, PageError> {
+        self.send_cmd(|response| Command::PageMarkdown {
+            selector: selector.map(|s| s.to_string()),
+            response,
+        })?
+    }
+
+    /// Collect every `<a href>` target on the page, resolved to an absolute
+    /// URL. See [`crate::engine::PageEngine::links`].
+    pub fn links(&self) -> Result<Vec<String>, PageError> {
+        self.send_cmd(|response| Command::Links { response })?
+    }
+
     pub fn console_messages(&self) -> Vec<ConsoleMessage> {
         self.send_cmd(|response| Command::ConsoleMessages { response })
             .unwrap_or_default()
     }
 
+    /// Drain and return uncaught JS exceptions and unhandled promise rejections
+    /// captured since the last call. See [`crate::engine::PageEngine::js_exceptions`].
+    pub fn js_exceptions(&self) -> Vec<JsException> {
+        self.send_cmd(|response| Command::JsExceptions { response })
+            .unwrap_or_default()
+    }
+
+    /// The [`NavigationError`] classified for the most recent [`Self::open`]/
+    /// [`Self::reload`] call. See
+    /// [`crate::engine::PageEngine::last_navigation_error`].
+    pub fn last_navigation_error(&self) -> Option<NavigationError> {
+        self.send_cmd(|response| Command::LastNavigationError { response })
+            .ok()
+            .flatten()
+    }
+
+    /// Begin JS coverage collection. See
+    /// [`crate::engine::PageEngine::start_js_coverage`].
+    pub fn start_js_coverage(&self) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::StartJsCoverage { response })?
+    }
+
+    /// Stop JS coverage collection. See
+    /// [`crate::engine::PageEngine::stop_js_coverage`].
+    pub fn stop_js_coverage(&self) -> Result<Vec<CoverageEntry>, PageError> {
+        self.send_cmd(|response| Command::StopJsCoverage { response })?
+    }
+
+    /// Begin CSS coverage collection. See
+    /// [`crate::engine::PageEngine::start_css_coverage`].
+    pub fn start_css_coverage(&self) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::StartCssCoverage { response })?
+    }
+
+    /// Stop CSS coverage collection. See
+    /// [`crate::engine::PageEngine::stop_css_coverage`].
+    pub fn stop_css_coverage(&self) -> Result<Vec<CoverageEntry>, PageError> {
+        self.send_cmd(|response| Command::StopCssCoverage { response })?
+    }
+
+    /// Drain and return captured JS dialogs. See
+    /// [`crate::engine::PageEngine::dialog_messages`].
+    pub fn dialog_messages(&self) -> Vec<Dialog> {
+        self.send_cmd(|response| Command::DialogMessages { response })
+            .unwrap_or_default()
+    }
+
     pub fn network_requests(&self) -> Vec<NetworkRequest> {
         self.send_cmd(|response| Command::NetworkRequests { response })
             .unwrap_or_default()
     }
 
+    /// See [`crate::engine::PageEngine::network_responses`].
+    pub fn network_responses(&self) -> Vec<NetworkRequest> {
+        self.send_cmd(|response| Command::NetworkResponses { response })
+            .unwrap_or_default()
+    }
+
+    /// See [`crate::engine::PageEngine::get_response_body`].
+    pub fn get_response_body(&self, url: &str) -> Option<Vec<u8>> {
+        self.send_cmd(|response| Command::GetResponseBody {
+            url: url.to_string(),
+            response,
+        })
+        .ok()
+        .flatten()
+    }
+
+    /// See [`crate::engine::PageEngine::response_body`].
+    pub fn response_body(&self, request_id: &str) -> Result<ResponseBody, PageError> {
+        self.send_cmd(|response| Command::ResponseBody {
+            request_id: request_id.to_string(),
+            response,
+        })?
+    }
+
+    pub fn intercepted_requests(&self) -> Vec<InterceptedRequest> {
+        self.send_cmd(|response| Command::InterceptedRequests { response })
+            .unwrap_or_default()
+    }
+
+    /// Drain captured network requests and serialize them as a HAR 1.2 log. See
+    /// [`crate::engine::PageEngine::har`] for the embedder-API limitations on response
+    /// data for requests this engine didn't fulfill itself.
+    pub fn har(&self) -> Result<String, PageError> {
+        self.send_cmd(|response| Command::Har { response })?
+    }
+
     pub fn close(&self) {
         let _ = self.send_cmd(|response| Command::Close { response });
     }
@@ -517,6 +1438,14 @@ impl Page {
         })?
     }
 
+    pub fn wait_for_selector_gone(&self, selector: &str, timeout: u64) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::WaitForSelectorGone {
+            selector: selector.to_string(),
+            timeout,
+            response,
+        })?
+    }
+
     pub fn wait_for_condition(&self, js_expr: &str, timeout: u64) -> Result<(), PageError> {
         self.send_cmd(|response| Command::WaitForCondition {
             js_expr: js_expr.to_string(),
@@ -552,6 +1481,15 @@ impl Page {
         })?
     }
 
+    /// Focus an element matching a CSS selector. See
+    /// [`crate::engine::PageEngine::focus`].
+    pub fn focus(&self, selector: &str) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::Focus {
+            selector: selector.to_string(),
+            response,
+        })?
+    }
+
     pub fn type_text(&self, text: &str) -> Result<(), PageError> {
         self.send_cmd(|response| Command::TypeText {
             text: text.to_string(),
@@ -605,8 +1543,8 @@ impl Page {
         self.send_cmd(|response| Command::GetCookies { response })?
     }
 
-    pub fn set_cookie(&self, cookie: &str) -> Result<(), PageError> {
-        self.send_cmd(|response| Command::SetCookie {
+    pub fn set_cookie_raw(&self, cookie: &str) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::SetCookieRaw {
             cookie: cookie.to_string(),
             response,
         })?
@@ -616,20 +1554,293 @@ impl Page {
         self.send_cmd(|response| Command::ClearCookies { response })?
     }
 
-    pub fn block_urls(&self, patterns: Vec<String>) {
-        let _ = self.send_cmd(|response| Command::BlockUrls { patterns, response });
+    /// Get all cookies visible to the page as typed [`Cookie`] values. See
+    /// [`crate::engine::PageEngine::cookies`] for the same `HttpOnly`/attribute caveats
+    /// as [`Self::get_cookies_json`].
+    pub fn cookies(&self) -> Result<Vec<Cookie>, PageError> {
+        self.send_cmd(|response| Command::Cookies { response })?
     }
 
-    pub fn clear_blocked_urls(&self) {
-        let _ = self.send_cmd(|response| Command::ClearBlockedUrls { response });
+    /// Set one cookie. See [`crate::engine::PageEngine::set_cookie`] — `HttpOnly`
+    /// cookies are rejected since they can't be created from script.
+    pub fn set_cookie(&self, cookie: &Cookie) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::SetCookie {
+            cookie: cookie.clone(),
+            response,
+        })?
     }
 
-    pub fn reload(&self) -> Result<(), PageError> {
-        self.send_cmd(|response| Command::Reload { response })?
+    /// Set multiple cookies, in order. See [`crate::engine::PageEngine::set_cookies`].
+    pub fn set_cookies(&self, cookies: &[Cookie]) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::SetCookies {
+            cookies: cookies.to_vec(),
+            response,
+        })?
     }
 
-    pub fn go_back(&self) -> Result<bool, PageError> {
-        self.send_cmd(|response| Command::GoBack { response })?
+    /// Register a script to run on every document. Returns an id that can be passed to
+    /// [`Self::remove_init_script`]. See
+    /// [`crate::engine::PageEngine::add_init_script`].
+    pub fn add_init_script(&self, script: impl Into<String>) -> u32 {
+        self.send_cmd(|response| Command::AddInitScript {
+            script: script.into(),
+            response,
+        })
+        .unwrap_or(0)
+    }
+
+    /// Remove a previously registered init script. See
+    /// [`crate::engine::PageEngine::remove_init_script`].
+    pub fn remove_init_script(&self, id: u32) -> bool {
+        self.send_cmd(|response| Command::RemoveInitScript { id, response })
+            .unwrap_or(false)
+    }
+
+    /// Expose a global JS function named `name` to page scripts. See
+    /// [`crate::engine::PageEngine::expose_function`]; `handler` must be `Send` here,
+    /// since it crosses this `Page`'s background-thread channel to run on the
+    /// engine's own thread.
+    pub fn expose_function<F>(&self, name: impl Into<String>, handler: F)
+    where
+        F: FnMut(String) -> Option<String> + Send + 'static,
+    {
+        let _ = self.send_cmd(|response| Command::ExposeFunction {
+            name: name.into(),
+            handler: Box::new(handler),
+            response,
+        });
+    }
+
+    /// Override `navigator.userAgent` for subsequent page loads. See
+    /// [`crate::engine::PageEngine::set_user_agent`].
+    pub fn set_user_agent(&self, user_agent: impl Into<String>) {
+        let _ = self.send_cmd(|response| Command::SetUserAgent {
+            user_agent: user_agent.into(),
+            response,
+        });
+    }
+
+    /// Replace the extra HTTP headers added to script-driven requests. See
+    /// [`crate::engine::PageEngine::set_extra_http_headers`].
+    pub fn set_extra_http_headers(&self, headers: HashMap<String, String>) {
+        let _ = self.send_cmd(|response| Command::SetExtraHttpHeaders { headers, response });
+    }
+
+    /// Alias for [`Self::set_extra_http_headers`], named to match the request that
+    /// introduced it.
+    pub fn set_extra_headers(&self, headers: HashMap<String, String>) {
+        self.set_extra_http_headers(headers);
+    }
+
+    /// Set (or replace) the HTTP Basic Auth credentials used for subsequent
+    /// navigations. See [`crate::engine::PageEngine::set_http_auth`].
+    pub fn set_http_auth(&self, username: impl Into<String>, password: impl Into<String>) {
+        let _ = self.send_cmd(|response| Command::SetHttpAuth {
+            username: username.into(),
+            password: password.into(),
+            response,
+        });
+    }
+
+    /// Alias for [`Self::set_http_auth`]. See
+    /// [`crate::engine::PageEngine::set_http_credentials`].
+    pub fn set_http_credentials(&self, username: impl Into<String>, password: impl Into<String>) {
+        self.set_http_auth(username, password);
+    }
+
+    /// Register a per-origin credential callback consulted before every navigation.
+    /// See [`crate::engine::PageEngine::on_auth_required`]; `callback` must be `Send`
+    /// here, since it crosses this `Page`'s background-thread channel to run on the
+    /// engine's own thread.
+    pub fn on_auth_required<F>(&self, callback: F)
+    where
+        F: FnMut(&str) -> Option<(String, String)> + Send + 'static,
+    {
+        let _ = self.send_cmd(|response| Command::OnAuthRequired {
+            callback: Box::new(callback),
+            response,
+        });
+    }
+
+    /// Override a `navigator` property for subsequent page loads. See
+    /// [`crate::engine::PageEngine::set_navigator_override`].
+    pub fn set_navigator_override(&self, field: &str, value: &str) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::SetNavigatorOverride {
+            field: field.to_string(),
+            value: value.to_string(),
+            response,
+        })?
+    }
+
+    /// Apply full device emulation to the active page. See
+    /// [`crate::engine::PageEngine::set_emulation`].
+    pub fn set_emulation(&self, settings: EmulationSettings) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::SetEmulation { settings, response })?
+    }
+
+    /// Apply a [`DeviceDescriptor`] preset (viewport, touch, and user-agent). See
+    /// [`crate::engine::PageEngine::emulate`].
+    pub fn emulate(&self, device: DeviceDescriptor) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::Emulate { device, response })?
+    }
+
+    /// Emulate `prefers-color-scheme`/print media for subsequent page loads. See
+    /// [`crate::engine::PageEngine::emulate_media`].
+    pub fn emulate_media(&self, media: MediaEmulation) {
+        let _ = self.send_cmd(|response| Command::EmulateMedia { media, response });
+    }
+
+    /// Override the `window.screen`/`devicePixelRatio` values JavaScript observes for
+    /// subsequent page loads. See [`crate::engine::PageEngine::set_viewport`].
+    pub fn set_viewport(&self, width: u32, height: u32, device_scale: f32) {
+        let _ = self.send_cmd(|response| Command::SetViewport {
+            width,
+            height,
+            device_scale,
+            response,
+        });
+    }
+
+    /// Get all cookies as a JSON array of structured `{name, value, domain, path,
+    /// expires, http_only, secure, same_site}` objects.
+    pub fn get_cookies_json(&self) -> Result<String, PageError> {
+        self.send_cmd(|response| Command::GetCookiesJson { response })?
+    }
+
+    /// Set one structured cookie from a JSON object (see
+    /// [`crate::engine::PageEngine::set_cookie_struct`]).
+    pub fn set_cookie_struct(&self, json: &str) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::SetCookieStruct {
+            json: json.to_string(),
+            response,
+        })?
+    }
+
+    /// Delete a single cookie by name, optionally scoped to a domain and path.
+    pub fn delete_cookie(
+        &self,
+        name: &str,
+        domain: Option<&str>,
+        path: Option<&str>,
+    ) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::DeleteCookie {
+            name: name.to_string(),
+            domain: domain.map(str::to_string),
+            path: path.map(str::to_string),
+            response,
+        })?
+    }
+
+    /// Search the rendered text of the page, highlighting matches. See
+    /// [`crate::engine::PageEngine::find_text`].
+    pub fn find_text(&self, query: &str, flags: u32) -> Result<u32, PageError> {
+        self.send_cmd(|response| Command::FindText {
+            query: query.to_string(),
+            flags,
+            response,
+        })?
+    }
+
+    /// Advance to the next match from a prior [`Self::find_text`] call, returning its
+    /// bounding rectangle.
+    pub fn find_next(&self) -> Result<ElementRect, PageError> {
+        self.send_cmd(|response| Command::FindNext { response })?
+    }
+
+    /// Move to the previous match from a prior [`Self::find_text`] call, returning its
+    /// bounding rectangle.
+    pub fn find_previous(&self) -> Result<ElementRect, PageError> {
+        self.send_cmd(|response| Command::FindPrevious { response })?
+    }
+
+    /// Remove all highlights left by [`Self::find_text`].
+    pub fn find_clear(&self) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::FindClear { response })?
+    }
+
+    /// Arm or disarm download capture. See
+    /// [`crate::engine::PageEngine::set_download_capture`].
+    pub fn set_download_capture(&self, enabled: bool) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::SetDownloadCapture { enabled, response })?
+    }
+
+    /// Block until at least `count` downloads have been captured. See
+    /// [`crate::engine::PageEngine::wait_for_downloads`].
+    pub fn wait_for_downloads(&self, count: usize, timeout: u64) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::WaitForDownloads {
+            count,
+            timeout,
+            response,
+        })?
+    }
+
+    /// List captured downloads as a JSON array of `{suggested_filename, mime_type,
+    /// url, size}` objects. Downloads are buffered asynchronously -- see
+    /// [`Self::wait_for_downloads`] if this is called right after triggering one.
+    pub fn get_downloads(&self) -> Result<String, PageError> {
+        self.send_cmd(|response| Command::GetDownloads { response })?
+    }
+
+    /// Flush the buffered bytes of a captured download to `dest_path` on disk.
+    pub fn save_download(&self, index: u32, dest_path: &str) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::SaveDownload {
+            index,
+            dest_path: dest_path.to_string(),
+            response,
+        })?
+    }
+
+    /// Register a handler invoked synchronously for every outgoing request whose URL
+    /// matches `pattern` (glob syntax, as in [`Self::block_urls`]), deciding whether it
+    /// continues, gets redirected, is fulfilled with a canned response, or fails. See
+    /// [`crate::engine::PageEngine::intercept_requests`] for the single-active-callback
+    /// caveat this shares with [`crate::engine::PageEngine::on_request`]. Unlike the
+    /// engine-level API, `handler` must be `Send` here, since it crosses this `Page`'s
+    /// background-thread channel to run on the engine's own thread.
+    pub fn intercept_requests<F>(&self, pattern: &str, handler: F)
+    where
+        F: FnMut(&NetworkRequest) -> RequestDecision + Send + 'static,
+    {
+        let _ = self.send_cmd(|response| Command::InterceptRequests {
+            pattern: pattern.to_string(),
+            handler: Box::new(handler),
+            response,
+        });
+    }
+
+    pub fn block_urls(&self, patterns: Vec<String>) {
+        let _ = self.send_cmd(|response| Command::BlockUrls { patterns, response });
+    }
+
+    pub fn clear_blocked_urls(&self) {
+        let _ = self.send_cmd(|response| Command::ClearBlockedUrls { response });
+    }
+
+    /// Register a routing rule (block/redirect/fulfill). See
+    /// [`crate::engine::PageEngine::add_route`].
+    pub fn add_route(&self, rule_json: &str) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::AddRoute {
+            rule_json: rule_json.to_string(),
+            response,
+        })?
+    }
+
+    /// Alias for [`Self::add_route`].
+    pub fn add_intercept_rule(&self, rule_json: &str) -> Result<(), PageError> {
+        self.add_route(rule_json)
+    }
+
+    /// Remove all registered routing rules.
+    pub fn clear_routes(&self) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::ClearRoutes { response })?
+    }
+
+    pub fn reload(&self) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::Reload { response })?
+    }
+
+    pub fn go_back(&self) -> Result<bool, PageError> {
+        self.send_cmd(|response| Command::GoBack { response })?
     }
 
     pub fn go_forward(&self) -> Result<bool, PageError> {
@@ -669,6 +1880,229 @@ impl Page {
         })?
     }
 
+    /// Like [`Self::element_rect`], but accepts any [`Locator`] (CSS or XPath). See
+    /// [`crate::engine::PageEngine::element_rect_by`].
+    pub fn element_rect_by(&self, locator: Locator) -> Result<ElementRect, PageError> {
+        self.send_cmd(|response| Command::ElementRectBy { locator, response })?
+    }
+
+    /// Like [`Self::element_text`], but accepts any [`Locator`] (CSS or XPath). See
+    /// [`crate::engine::PageEngine::element_text_by`].
+    pub fn element_text_by(&self, locator: Locator) -> Result<String, PageError> {
+        self.send_cmd(|response| Command::ElementTextBy { locator, response })?
+    }
+
+    /// Like [`Self::element_attribute`], but accepts any [`Locator`] (CSS or XPath).
+    /// See [`crate::engine::PageEngine::element_attribute_by`].
+    pub fn element_attribute_by(
+        &self,
+        locator: Locator,
+        attribute: &str,
+    ) -> Result<Option<String>, PageError> {
+        self.send_cmd(|response| Command::ElementAttributeBy {
+            locator,
+            attribute: attribute.to_string(),
+            response,
+        })?
+    }
+
+    /// Like [`Self::element_html`], but accepts any [`Locator`] (CSS or XPath). See
+    /// [`crate::engine::PageEngine::element_html_by`].
+    pub fn element_html_by(&self, locator: Locator) -> Result<String, PageError> {
+        self.send_cmd(|response| Command::ElementHtmlBy { locator, response })?
+    }
+
+    /// Bounding rectangles of every matching element, as a JSON array. See
+    /// [`crate::engine::PageEngine::elements_rect`].
+    pub fn elements_rect(&self, selector: &str) -> Result<String, PageError> {
+        self.send_cmd(|response| Command::ElementsRect {
+            selector: selector.to_string(),
+            response,
+        })?
+    }
+
+    /// Text content of every matching element, as a JSON array. See
+    /// [`crate::engine::PageEngine::elements_text`].
+    pub fn elements_text(&self, selector: &str) -> Result<String, PageError> {
+        self.send_cmd(|response| Command::ElementsText {
+            selector: selector.to_string(),
+            response,
+        })?
+    }
+
+    /// An attribute value of every matching element, as a JSON array. See
+    /// [`crate::engine::PageEngine::elements_attribute`].
+    pub fn elements_attribute(&self, selector: &str, attribute: &str) -> Result<String, PageError> {
+        self.send_cmd(|response| Command::ElementsAttribute {
+            selector: selector.to_string(),
+            attribute: attribute.to_string(),
+            response,
+        })?
+    }
+
+    /// Outer HTML of every matching element, as a JSON array. See
+    /// [`crate::engine::PageEngine::elements_html`].
+    pub fn elements_html(&self, selector: &str) -> Result<String, PageError> {
+        self.send_cmd(|response| Command::ElementsHtml {
+            selector: selector.to_string(),
+            response,
+        })?
+    }
+
+    /// Rect, text, outer HTML, and attributes of every matching element, in a single
+    /// round-trip. See [`crate::engine::PageEngine::query_all`].
+    pub fn query_all(&self, selector: &str) -> Result<Vec<ElementInfo>, PageError> {
+        self.send_cmd(|response| Command::QueryAll {
+            selector: selector.to_string(),
+            response,
+        })?
+    }
+
+    /// Like [`Self::query_all`], but for just the first matching element. See
+    /// [`crate::engine::PageEngine::element_info`].
+    pub fn element_info(&self, selector: &str) -> Result<ElementInfo, PageError> {
+        self.send_cmd(|response| Command::ElementInfo {
+            selector: selector.to_string(),
+            response,
+        })?
+    }
+
+    /// Find the first element matching `selector` and return a handle scoped to it.
+    /// See [`crate::engine::PageEngine::find`].
+    pub fn find(&self, selector: &str) -> Result<Option<ElementHandle>, PageError> {
+        self.send_cmd(|response| Command::Find {
+            selector: selector.to_string(),
+            response,
+        })?
+    }
+
+    /// Find every element matching `selector` and return a handle scoped to each. See
+    /// [`crate::engine::PageEngine::find_all`].
+    pub fn find_all(&self, selector: &str) -> Result<Vec<ElementHandle>, PageError> {
+        self.send_cmd(|response| Command::FindAll {
+            selector: selector.to_string(),
+            response,
+        })?
+    }
+
+    /// Text content of the element a handle points to. See
+    /// [`crate::engine::PageEngine::handle_text`].
+    pub fn handle_text(&self, handle: &ElementHandle) -> Result<String, PageError> {
+        self.send_cmd(|response| Command::HandleText {
+            handle: handle.clone(),
+            response,
+        })?
+    }
+
+    /// An attribute value of the element a handle points to. See
+    /// [`crate::engine::PageEngine::handle_attribute`].
+    pub fn handle_attribute(
+        &self,
+        handle: &ElementHandle,
+        attribute: &str,
+    ) -> Result<Option<String>, PageError> {
+        self.send_cmd(|response| Command::HandleAttribute {
+            handle: handle.clone(),
+            attribute: attribute.to_string(),
+            response,
+        })?
+    }
+
+    /// Bounding rectangle of the element a handle points to. See
+    /// [`crate::engine::PageEngine::handle_bounding_box`].
+    pub fn handle_bounding_box(&self, handle: &ElementHandle) -> Result<ElementRect, PageError> {
+        self.send_cmd(|response| Command::HandleBoundingBox {
+            handle: handle.clone(),
+            response,
+        })?
+    }
+
+    /// Click the element a handle points to. See
+    /// [`crate::engine::PageEngine::handle_click`].
+    pub fn handle_click(&self, handle: &ElementHandle) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::HandleClick {
+            handle: handle.clone(),
+            response,
+        })?
+    }
+
+    /// Click the element a handle points to, then type text into it. See
+    /// [`crate::engine::PageEngine::handle_type_text`].
+    pub fn handle_type_text(&self, handle: &ElementHandle, text: &str) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::HandleTypeText {
+            handle: handle.clone(),
+            text: text.to_string(),
+            response,
+        })?
+    }
+
+    /// A single structured snapshot combining the page URL, title, and every field
+    /// resolved from `spec_json` in one pass. See
+    /// [`crate::engine::PageEngine::snapshot`].
+    pub fn snapshot(&self, spec_json: &str) -> Result<String, PageError> {
+        self.send_cmd(|response| Command::Snapshot {
+            spec_json: spec_json.to_string(),
+            response,
+        })?
+    }
+
+    /// Serialize the live DOM into a fully self-contained HTML document, with every
+    /// subresource inlined as a `data:` URI. `flags` is a bitmask of
+    /// [`crate::types::archive_flags`] values.
+    pub fn save_archive(&self, flags: u32) -> Result<String, PageError> {
+        self.send_cmd(|response| Command::SaveArchive { flags, response })?
+    }
+
+    /// Run a readability-style extraction over the loaded page. See
+    /// [`crate::engine::PageEngine::extract_article`].
+    pub fn extract_article(&self) -> Result<Article, PageError> {
+        self.send_cmd(|response| Command::ExtractArticle { response })?
+    }
+
+    /// Extract the page's main article content and package it, with its images,
+    /// as a single EPUB file at `dest_path`. See
+    /// [`crate::engine::PageEngine::save_epub`].
+    pub fn save_epub(&self, dest_path: &str) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::SaveEpub {
+            dest_path: dest_path.to_string(),
+            response,
+        })?
+    }
+
+    /// Execute a W3C WebDriver-style batched Actions payload (see
+    /// [`crate::engine::PageEngine::perform_actions`] for the JSON shape).
+    pub fn perform_actions(&self, json: &str) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::PerformActions {
+            json: json.to_string(),
+            response,
+        })?
+    }
+
+    /// Execute a typed [`crate::types::Action`] sequence (see
+    /// [`crate::engine::PageEngine::perform_action_sequence`]).
+    pub fn perform_action_sequence(&self, actions: Vec<Action>) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::PerformActionSequence { actions, response })?
+    }
+
+    /// Start building a chained [`Action`] timeline (see
+    /// [`crate::engine::PageEngine::actions`]).
+    pub fn actions(&self) -> ActionsBuilder<'_> {
+        ActionsBuilder {
+            page: self,
+            actions: Vec::new(),
+        }
+    }
+
+    /// Resolve `selector`, focus it, and type `text` into it (see
+    /// [`crate::engine::PageEngine::type_text_selector`]).
+    pub fn type_text_selector(&self, selector: &str, text: &str) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::TypeTextSelector {
+            selector: selector.to_string(),
+            text: text.to_string(),
+            response,
+        })?
+    }
+
     // -- Multi-page methods --
 
     /// Create a new page with the default viewport size. Returns the page ID.
@@ -695,6 +2129,18 @@ impl Page {
         self.send_cmd(|response| Command::ClosePage { page_id, response })?
     }
 
+    /// Tear down a non-active page's document/layout state to reclaim memory. See
+    /// [`crate::engine::PageEngine::discard_page`].
+    pub fn discard_page(&self, page_id: u32) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::DiscardPage { page_id, response })?
+    }
+
+    /// Query whether a page is [`PageLifecycle::Live`] or [`PageLifecycle::Discarded`].
+    /// See [`crate::engine::PageEngine::page_state`].
+    pub fn page_state(&self, page_id: u32) -> Result<PageLifecycle, PageError> {
+        self.send_cmd(|response| Command::PageState { page_id, response })?
+    }
+
     /// Get the active page ID, or `None` if no page is active.
     pub fn active_page_id(&self) -> Option<u32> {
         self.send_cmd(|response| Command::ActivePageId { response })
@@ -714,11 +2160,33 @@ impl Page {
             .unwrap_or(0)
     }
 
-    /// Enable or disable popup capture.
+    /// Enable or disable popup capture. Alias for [`Self::set_popup_policy`] -- see
+    /// [`crate::PageEngine::set_popup_handling`].
     pub fn set_popup_handling(&self, enabled: bool) {
         let _ = self.send_cmd(|response| Command::SetPopupHandling { enabled, response });
     }
 
+    /// Set how a popup (`window.open`, a `target="_blank"` link, etc.) is handled --
+    /// block it, capture it for [`Self::popup_pages`] to drain, or redirect this
+    /// page's own WebView to the popup's URL instead. See [`crate::PageEngine::set_popup_policy`].
+    pub fn set_popup_policy(&self, policy: PopupPolicy) {
+        let _ = self.send_cmd(|response| Command::SetPopupPolicy { policy, response });
+    }
+
+    /// Subscribe to a push-based [`PopupEvent`] stream instead of polling
+    /// [`Self::popup_pages`] -- delivers `Opened`/`Closed` as they happen, including
+    /// for a popup that opens and closes again before the next poll would have seen
+    /// it. See [`crate::PageEngine::popup_events`].
+    pub fn popup_events(&self) -> Result<mpsc::Receiver<PopupEvent>, PageError> {
+        self.send_cmd(|response| Command::PopupEvents { response })
+    }
+
+    /// Turn response-body capture on or off at runtime. See
+    /// [`crate::engine::PageEngine::capture_response_bodies`].
+    pub fn capture_response_bodies(&self, enabled: bool) {
+        let _ = self.send_cmd(|response| Command::CaptureResponseBodies { enabled, response });
+    }
+
     /// Drain pending popup WebViews and return their page IDs.
     pub fn popup_pages(&self) -> Vec<u32> {
         self.send_cmd(|response| Command::PopupPages { response })
@@ -738,6 +2206,33 @@ impl Page {
             .ok()
             .flatten()
     }
+
+    /// Render a specific page's current viewport to a PDF, without switching the
+    /// active page. See [`Self::print_to_pdf`] for the active-page convenience
+    /// wrapper.
+    pub fn page_to_pdf(&self, page_id: u32, opts: PdfOptions) -> Result<Vec<u8>, PageError> {
+        self.send_cmd(|response| Command::PageToPdf {
+            page_id,
+            opts,
+            response,
+        })?
+    }
+
+    /// Subscribe to a push-based [`PageEvent`] stream instead of polling
+    /// [`Self::console_messages`]/[`Self::network_requests`] -- see
+    /// [`crate::PageEngine::subscribe`] for the bitset semantics and the returned
+    /// receiver's iterator usage, e.g. `for event in page.events(event_kinds::ALL)? { ... }`.
+    /// Replaces any previous subscription. Dropping the receiver is only noticed
+    /// lazily, the next time an event would have been pushed to it; call
+    /// [`Self::unsubscribe`] for an immediate, explicit deregistration instead.
+    pub fn events(&self, kinds: u32) -> Result<mpsc::Receiver<PageEvent>, PageError> {
+        self.send_cmd(|response| Command::Subscribe { kinds, response })
+    }
+
+    /// Stop the active event subscription (see [`Self::events`]).
+    pub fn unsubscribe(&self) -> Result<(), PageError> {
+        self.send_cmd(|response| Command::Unsubscribe { response })
+    }
 }
 
 impl Drop for Page {
@@ -751,3 +2246,250 @@ impl Drop for Page {
         }
     }
 }
+
+/// A chained builder for composing an [`Action`] timeline, returned by
+/// [`Page::actions`]. Each call appends one tick; [`Self::perform`] dispatches
+/// the whole timeline in a single [`Page::perform_action_sequence`] command.
+pub struct ActionsBuilder<'a> {
+    page: &'a Page,
+    actions: Vec<Action>,
+}
+
+impl<'a> ActionsBuilder<'a> {
+    /// Move the pointer to absolute device coordinates, interpolated over `duration`.
+    pub fn pointer_move(mut self, x: f32, y: f32, duration: Duration) -> Self {
+        self.actions.push(Action::MoveTo { x, y, duration });
+        self
+    }
+
+    /// Press a mouse button down at the current pointer position.
+    pub fn pointer_down(mut self, button: PointerButton) -> Self {
+        self.actions.push(Action::MouseDown(button));
+        self
+    }
+
+    /// Release a mouse button at the current pointer position.
+    pub fn pointer_up(mut self, button: PointerButton) -> Self {
+        self.actions.push(Action::MouseUp(button));
+        self
+    }
+
+    /// Press a key down. See [`Action::KeyDown`] for accepted key names.
+    pub fn key_down(mut self, key: impl Into<String>) -> Self {
+        self.actions.push(Action::KeyDown(key.into()));
+        self
+    }
+
+    /// Release a key.
+    pub fn key_up(mut self, key: impl Into<String>) -> Self {
+        self.actions.push(Action::KeyUp(key.into()));
+        self
+    }
+
+    /// Let the event loop spin for `duration` before the next tick.
+    pub fn pause(mut self, duration: Duration) -> Self {
+        self.actions.push(Action::Pause(duration));
+        self
+    }
+
+    /// Scroll the viewport by the given pixel deltas. See [`Action::Scroll`].
+    pub fn scroll(mut self, delta_x: f64, delta_y: f64) -> Self {
+        self.actions.push(Action::Scroll { delta_x, delta_y });
+        self
+    }
+
+    /// Dispatch the accumulated timeline via [`Page::perform_action_sequence`].
+    pub fn perform(self) -> Result<(), PageError> {
+        self.page.perform_action_sequence(self.actions)
+    }
+}
+
+// ===========================================================================
+// Crawler: breadth-first link-following walk on top of Page
+// ===========================================================================
+
+/// Configuration for [`Crawler::crawl`].
+#[derive(Debug, Clone)]
+pub struct CrawlConfig {
+    /// Maximum link depth from the seed URL (the seed itself is depth 0).
+    pub max_depth: u32,
+    /// Stop once this many pages have been visited, regardless of depth.
+    pub max_pages: usize,
+    /// If `true`, only enqueue links whose host matches the seed URL's host.
+    pub same_domain_only: bool,
+    /// If non-empty, a link is only enqueued if at least one pattern matches.
+    pub include: Vec<Regex>,
+    /// A link matching any pattern here is never enqueued, even if `include`
+    /// would otherwise allow it.
+    pub exclude: Vec<Regex>,
+    /// How long to wait between pages, to avoid hammering the target site.
+    pub delay: Duration,
+    /// If `true`, also enqueue URLs opened via `window.open`/`target="_blank"`
+    /// that the popup-capture machinery recorded for the visited page (see
+    /// [`Page::popup_pages`]).
+    pub follow_popups: bool,
+}
+
+impl Default for CrawlConfig {
+    fn default() -> Self {
+        CrawlConfig {
+            max_depth: 3,
+            max_pages: 100,
+            same_domain_only: true,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            delay: Duration::ZERO,
+            follow_popups: false,
+        }
+    }
+}
+
+/// One page visited by [`Crawler::crawl`].
+#[derive(Debug, Clone)]
+pub struct CrawlRecord {
+    pub url: String,
+    pub depth: u32,
+    pub title: Option<String>,
+    /// Every `<a href>` target found on the page (see [`Page::links`]),
+    /// before filtering -- not just the ones that passed `CrawlConfig` and
+    /// got enqueued.
+    pub links: Vec<String>,
+    /// Whatever `on_page` returned, if anything -- typically the result of a
+    /// [`Page::markdown`] or [`Page::snapshot`] call made from inside the
+    /// callback.
+    pub extracted: Option<String>,
+}
+
+/// Breadth-first link-following crawler built on top of [`Page`]. Loads a seed
+/// URL, collects every `<a href>` it reaches via [`Page::links`], and walks
+/// the frontier subject to [`CrawlConfig`]'s depth/page-count/domain/regex
+/// limits -- the navigation loop a whole-site harvest would otherwise leave
+/// every caller to hand-write against the single-page `Page` API.
+pub struct Crawler {
+    page: Page,
+    config: CrawlConfig,
+}
+
+impl Crawler {
+    /// Wrap `page` as a crawler driven by `config`. `page` is reused for every
+    /// page in the walk -- the crawler just drives `Page::open` in a loop.
+    pub fn new(page: Page, config: CrawlConfig) -> Self {
+        Crawler { page, config }
+    }
+
+    /// Borrow the underlying [`Page`], e.g. to call [`Page::markdown`] or
+    /// [`Page::snapshot`] from inside `on_page` against whatever is currently
+    /// loaded.
+    pub fn page(&self) -> &Page {
+        &self.page
+    }
+
+    /// Crawl breadth-first from `seed_url`. `on_page` is called once per
+    /// visited page, with the page still loaded in the wrapped [`Page`]; its
+    /// return value (e.g. extracted Markdown or a JSON snapshot) is attached
+    /// to the returned [`CrawlRecord`] as `extracted`. A page that fails to
+    /// load is skipped rather than aborting the whole crawl.
+    pub fn crawl(
+        &self,
+        seed_url: &str,
+        mut on_page: impl FnMut(&Page, &CrawlRecord) -> Option<String>,
+    ) -> Vec<CrawlRecord> {
+        let seed = normalize_url(seed_url);
+        let seed_domain = domain_of(&seed);
+
+        let mut visited: HashSet<String> = HashSet::new();
+        let mut queue: VecDeque<(String, u32)> = VecDeque::new();
+        visited.insert(seed.clone());
+        queue.push_back((seed, 0));
+
+        let mut records = Vec::new();
+        while let Some((url, depth)) = queue.pop_front() {
+            if records.len() >= self.config.max_pages {
+                break;
+            }
+            if self.page.open(&url).is_err() {
+                continue;
+            }
+
+            let title = self.page.title();
+            let links = self.page.links().unwrap_or_default();
+
+            let mut targets: Vec<String> = links
+                .iter()
+                .map(|l| normalize_url(l))
+                .filter(|l| self.passes_filters(l, seed_domain.as_deref()))
+                .collect();
+            if self.config.follow_popups {
+                for popup_id in self.page.popup_pages() {
+                    if let Some(popup_url) = self.page.page_url(popup_id) {
+                        let popup_url = normalize_url(&popup_url);
+                        if self.passes_filters(&popup_url, seed_domain.as_deref()) {
+                            targets.push(popup_url);
+                        }
+                    }
+                }
+            }
+
+            let mut record = CrawlRecord {
+                url,
+                depth,
+                title,
+                links,
+                extracted: None,
+            };
+            record.extracted = on_page(&self.page, &record);
+
+            if depth < self.config.max_depth {
+                for target in targets.drain(..) {
+                    if visited.insert(target.clone()) {
+                        queue.push_back((target, depth + 1));
+                    }
+                }
+            }
+
+            records.push(record);
+
+            if !self.config.delay.is_zero() {
+                self.page.wait(self.config.delay.as_secs_f64());
+            }
+        }
+
+        records
+    }
+
+    fn passes_filters(&self, url: &str, seed_domain: Option<&str>) -> bool {
+        if self.config.same_domain_only {
+            if let Some(seed_domain) = seed_domain {
+                if domain_of(url).as_deref() != Some(seed_domain) {
+                    return false;
+                }
+            }
+        }
+        if !self.config.include.is_empty() && !self.config.include.iter().any(|re| re.is_match(url)) {
+            return false;
+        }
+        if self.config.exclude.iter().any(|re| re.is_match(url)) {
+            return false;
+        }
+        true
+    }
+}
+
+/// Resolve to an absolute URL with its fragment stripped, so `#section`
+/// variants of the same page dedupe against each other. Falls back to the
+/// input unchanged if it doesn't parse as a URL.
+fn normalize_url(url: &str) -> String {
+    match url::Url::parse(url) {
+        Ok(mut parsed) => {
+            parsed.set_fragment(None);
+            parsed.to_string()
+        }
+        Err(_) => url.to_string(),
+    }
+}
+
+fn domain_of(url: &str) -> Option<String> {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_string()))
+}
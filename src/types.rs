@@ -4,9 +4,12 @@
 
 //! Shared public types used across all layers.
 
+use std::collections::HashMap;
 use std::fmt;
+use std::time::Duration;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 /// Options for configuring a page session.
 #[derive(Debug, Clone)]
@@ -21,6 +24,54 @@ pub struct PageOptions {
     pub wait: f64,
     /// Capture the full scrollable page, not just the viewport (default: false).
     pub fullpage: bool,
+    /// Override the `User-Agent` sent on every request (default: none, Servo's own).
+    pub user_agent: Option<String>,
+    /// Request rules applied to every page created with these options, e.g. to block
+    /// images/fonts/analytics up front without a follow-up `add_route` call
+    /// (default: empty).
+    pub request_rules: Vec<RequestRule>,
+    /// Cookies set once the first `open()` call's navigation completes, so pages
+    /// behind a session login or consent wall see them on every request from then on
+    /// (default: empty). Can't actually precede the very first navigation — see
+    /// [`crate::PageEngine::open`] — and is subject to the same `HttpOnly` limitation
+    /// as [`crate::PageEngine::set_cookie`].
+    pub cookies: Vec<Cookie>,
+    /// Extra HTTP headers applied to `fetch`/`XMLHttpRequest` calls the page itself
+    /// makes after navigation (default: empty). The embedding API here has no hook to
+    /// rewrite the headers of the initial document/resource requests Servo issues —
+    /// see [`crate::PageEngine::open`] — so this covers script-initiated requests only.
+    pub extra_headers: Vec<(String, String)>,
+    /// HTTP basic auth credentials (default: none). Applied as `user:pass@host`
+    /// userinfo on the navigated URL for the top-level request, and as an
+    /// `Authorization` header on script-initiated `fetch`/`XMLHttpRequest` calls.
+    pub basic_auth: Option<(String, String)>,
+    /// JavaScript evaluated against every document, akin to CDP's
+    /// `Page.addScriptToEvaluateOnNewDocument` (default: empty). See
+    /// [`crate::PageEngine::add_init_script`] for the caveats on *how* early it runs.
+    pub init_scripts: Vec<String>,
+    /// Capture response bodies in [`crate::PageEngine::har`] entries (default: false).
+    /// Bodies are only ever knowable for requests this engine itself fulfilled via
+    /// [`crate::PageEngine::on_request`] or [`crate::PageEngine::add_route`] — see
+    /// [`NetworkRequest::body`].
+    pub capture_bodies: bool,
+    /// Largest response body, in bytes, that `capture_bodies` will record in full
+    /// (default: 2 MiB). Larger bodies are truncated to this size rather than dropped
+    /// -- see [`NetworkRequest::was_truncated`].
+    pub max_body_capture_bytes: usize,
+    /// Device scale factor to emulate, e.g. `2.0`/`3.0` for retina-resolution output
+    /// (default: 1.0). The rendering surface is sized to `width*dpr x height*dpr`
+    /// physical pixels while CSS layout and `window.devicePixelRatio` still see the
+    /// logical `width x height`. Only applied to the primary page and pages created
+    /// via `new_page`/`new_page_with_size`, not to popups, which always render at 1.0.
+    pub device_scale_factor: f32,
+    /// Maximum number of pages kept hydrated (live `Document`/layout state) at once
+    /// (default: none, no limit). Once exceeded, [`crate::PageEngine::new_page`]/
+    /// [`crate::PageEngine::new_page_with_size`] automatically
+    /// [`crate::PageEngine::discard_page`]s the least-recently-activated inactive page
+    /// to make room -- mirroring how Servo itself discards inactive documents to
+    /// reclaim memory. A discarded page is reloaded transparently, at the cost of a
+    /// fresh navigation, the next time [`crate::PageEngine::switch_to`] activates it.
+    pub max_live_pages: Option<usize>,
 }
 
 impl Default for PageOptions {
@@ -31,10 +82,641 @@ impl Default for PageOptions {
             timeout: 30,
             wait: 2.0,
             fullpage: false,
+            user_agent: None,
+            request_rules: Vec::new(),
+            cookies: Vec::new(),
+            extra_headers: Vec::new(),
+            basic_auth: None,
+            init_scripts: Vec::new(),
+            capture_bodies: false,
+            max_body_capture_bytes: 2 * 1024 * 1024,
+            device_scale_factor: 1.0,
+            max_live_pages: None,
         }
     }
 }
 
+/// A browser cookie, modeled on the WebDriver/CDP cookie shape. See
+/// [`crate::PageEngine::cookies`] and [`crate::PageEngine::set_cookie`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cookie {
+    pub name: String,
+    pub value: String,
+    #[serde(default)]
+    pub domain: Option<String>,
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Expiry as Unix time in milliseconds.
+    #[serde(default)]
+    pub expires: Option<i64>,
+    #[serde(default)]
+    pub secure: bool,
+    /// Can only be observed/set true for cookies set server-side; `set_cookie` rejects
+    /// `true` here since `HttpOnly` cookies can't be created from script.
+    #[serde(default)]
+    pub http_only: bool,
+    #[serde(default)]
+    pub same_site: Option<String>,
+}
+
+/// A coarse request category, inferred from the URL when the embedder API doesn't
+/// expose the real request destination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResourceKind {
+    Document,
+    Stylesheet,
+    Image,
+    Script,
+    Xhr,
+    Font,
+}
+
+impl ResourceKind {
+    /// The string tag used internally to scope [`crate::RouteRule`]s.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResourceKind::Document => "document",
+            ResourceKind::Stylesheet => "stylesheet",
+            ResourceKind::Image => "image",
+            ResourceKind::Script => "script",
+            ResourceKind::Xhr => "xhr",
+            ResourceKind::Font => "font",
+        }
+    }
+}
+
+/// A static request-blocking rule set up front via [`PageOptions::request_rules`],
+/// for the common case of blocking images/fonts/analytics to speed up scraping and
+/// reduce bandwidth. For redirects, fulfillment, or per-request decisions, use
+/// `PageEngine::add_route` or `PageEngine::on_request` instead.
+#[derive(Debug, Clone)]
+pub struct RequestRule {
+    /// A glob pattern (`*` wildcards) matched against the request URL.
+    pub pattern: String,
+    /// Restrict the rule to one resource kind. `None` matches any kind.
+    pub resource_kind: Option<ResourceKind>,
+}
+
+/// What to do with a request passed to a [`crate::PageEngine::on_request`] callback.
+#[derive(Debug, Clone)]
+pub enum RequestDecision {
+    /// Let the request proceed unchanged.
+    Continue,
+    /// Cancel the request.
+    Abort,
+    /// Cancel the request, same as [`Self::Abort`], carrying a human-readable reason
+    /// recorded on the [`crate::types::InterceptedRequest`] audit log entry. Mirrors
+    /// the CDP Fetch domain's `Fetch.failRequest`, but since there's no network-error
+    /// taxonomy in this embedding API to fail *with* (no `net::ERR_*`-style codes
+    /// surfaced by `load.intercept(..).cancel()`), `reason` is free text rather than a
+    /// typed error code -- every simulated failure looks like a plain cancelled load
+    /// to the page either way.
+    Fail { reason: String },
+    /// Respond with a 302 redirect to the given URL.
+    Redirect(String),
+    /// Respond directly with a status code, headers, and a body.
+    Fulfill {
+        status: u16,
+        headers: HashMap<String, String>,
+        body: Vec<u8>,
+    },
+    /// Answer an HTTP basic-auth challenge with the given credentials.
+    ///
+    /// Treated identically to [`Self::Continue`]: like rewriting headers on a
+    /// continuing request (see the [`crate::PageEngine::on_request`] docs), there's no
+    /// hook in this embedding API to attach an `Authorization` header to a request
+    /// already in flight, and no native auth-challenge dialog is ever raised through
+    /// `show_embedder_control` for this crate to react to in the first place --
+    /// [`crate::PageOptions::basic_auth`] is the only way credentials actually reach a
+    /// request, applied proactively up front rather than in response to a challenge.
+    Auth { username: String, password: String },
+}
+
+/// Which native JS dialog a [`Dialog`] represents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum DialogKind {
+    /// `window.alert(message)`.
+    Alert,
+    /// `window.confirm(message)`.
+    Confirm,
+    /// `window.prompt(message, default)`.
+    Prompt,
+    /// A navigation-away guard registered via `window.onbeforeunload`.
+    ///
+    /// Never actually surfaced: this embedding API's `show_embedder_control` hook --
+    /// the same one [`crate::PageEngine::set_dialog_handler`] listens on for
+    /// `Alert`/`Confirm`/`Prompt` -- has no `beforeunload` variant, and no other
+    /// `WebViewDelegate` hook exposes one either, so there's nothing to intercept a
+    /// tab-close/navigation guard with. Kept in the enum for forward-compatibility and
+    /// so a callback written against this API doesn't need updating if that changes.
+    BeforeUnload,
+}
+
+/// A JS dialog awaiting a response, passed to a callback registered via
+/// [`crate::PageEngine::set_dialog_handler`], and buffered (drained) by
+/// [`crate::PageEngine::dialog_messages`].
+#[derive(Debug, Clone, Serialize)]
+pub struct Dialog {
+    pub kind: DialogKind,
+    pub message: String,
+}
+
+/// How to resolve a [`Dialog`] passed to a [`crate::PageEngine::set_dialog_handler`]
+/// callback.
+#[derive(Debug, Clone)]
+pub enum DialogAction {
+    /// Accept the dialog. `prompt_text` supplies the value `window.prompt` resolves
+    /// to; ignored for `Alert`/`Confirm`.
+    Accept { prompt_text: Option<String> },
+    /// Dismiss (cancel) the dialog.
+    Dismiss,
+}
+
+/// A mouse button used in a typed [`Action`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerButton {
+    Left,
+    Middle,
+    Right,
+}
+
+/// One step in a typed, Rust-native input sequence passed to
+/// [`crate::PageEngine::perform_action_sequence`]. Covers the same vocabulary as the
+/// WebDriver-style JSON payload accepted by [`crate::PageEngine::perform_actions`]
+/// (pointer moves/buttons, key presses, pauses), but actions run strictly in the
+/// order given rather than as parallel per-source ticks — simpler, and sufficient
+/// for the common case of one pointer and one keyboard driving a page.
+#[derive(Debug, Clone)]
+pub enum Action {
+    /// Move the pointer to absolute device coordinates. If `duration` is non-zero,
+    /// the move is interpolated into several intermediate mouse-move events over
+    /// that span instead of jumping straight there.
+    MoveTo {
+        x: f32,
+        y: f32,
+        duration: Duration,
+    },
+    /// Press a mouse button down at the current pointer position.
+    MouseDown(PointerButton),
+    /// Release a mouse button at the current pointer position.
+    MouseUp(PointerButton),
+    /// Press a key down, mapped through the same key names `perform_actions` accepts
+    /// (e.g. `"Enter"`, `"Tab"`, or a single character).
+    KeyDown(String),
+    /// Release a key.
+    KeyUp(String),
+    /// Let the event loop spin for `duration` before the next action.
+    Pause(Duration),
+    /// Scroll the viewport by the given pixel deltas at the current pointer position,
+    /// via a native wheel event -- same convention as [`crate::PageEngine::scroll`]
+    /// (positive `delta_y` scrolls down).
+    Scroll { delta_x: f64, delta_y: f64 },
+}
+
+/// Page orientation for [`crate::PageEngine::print_to_pdf`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    Portrait,
+    Landscape,
+}
+
+/// Hydration state of a page, reported by [`crate::PageEngine::page_state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum PageLifecycle {
+    /// Has a live `WebView` with real document/layout state.
+    Live,
+    /// [`crate::PageEngine::discard_page`] has torn down its document/layout state;
+    /// [`crate::PageEngine::switch_to`] will transparently reload its last URL the next
+    /// time it's activated.
+    Discarded,
+}
+
+/// One entry in [`PdfOptions::page_ranges`], 1-indexed like the WebDriver print
+/// parameters this type mirrors (e.g. `[Single(1), Range(3, 8)]` for "1, 3-8").
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageRange {
+    Single(u32),
+    Range(u32, u32),
+}
+
+impl PageRange {
+    fn contains(&self, page: u32) -> bool {
+        match *self {
+            PageRange::Single(p) => p == page,
+            PageRange::Range(start, end) => (start..=end).contains(&page),
+        }
+    }
+}
+
+/// An axis-aligned clip region in device pixels for [`crate::PageEngine::screenshot_with`],
+/// mirroring CDP's `CaptureScreenshot` `clip` parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClipRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Output format for [`crate::PageEngine::screenshot_with`], mirroring the
+/// `CaptureScreenshotFormat` options in the headless_chrome CDP bindings. `quality`
+/// is in the usual `0..=100` encoder range and is ignored by [`ScreenshotFormat::Png`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenshotFormat {
+    Png,
+    Jpeg { quality: u8 },
+    WebP { quality: u8 },
+}
+
+impl Default for ScreenshotFormat {
+    fn default() -> Self {
+        ScreenshotFormat::Png
+    }
+}
+
+/// Options for [`crate::PageEngine::screenshot_with`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScreenshotOptions {
+    pub format: ScreenshotFormat,
+    /// Crop to this sub-region instead of the full captured viewport/page.
+    pub clip: Option<ClipRect>,
+    /// Render with a transparent background instead of the page's own (typically
+    /// white) background — done by temporarily clearing `html`/`body` background
+    /// color before capture. See [`crate::PageEngine::screenshot_with`] for the
+    /// caveat this implies.
+    pub omit_background: bool,
+}
+
+/// A `(start, end)` byte offset range within a [`CoverageEntry::text`] that was used,
+/// per [`crate::PageEngine::stop_js_coverage`]/[`crate::PageEngine::stop_css_coverage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoverageRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Coverage for one loaded script or stylesheet, returned by
+/// [`crate::PageEngine::stop_js_coverage`]/[`crate::PageEngine::stop_css_coverage`].
+/// See those methods for what granularity each actually achieves.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverageEntry {
+    /// Source URL, or `"(inline)"` for a script/style element with no `src`/`href`.
+    pub url: String,
+    /// The full source text this entry's `ranges` are offsets into.
+    pub text: String,
+    pub ranges: Vec<CoverageRange>,
+}
+
+/// Options for [`crate::compare_screenshots`]/[`crate::PageEngine::screenshot_diff`].
+#[derive(Debug, Clone, Copy)]
+pub struct DiffOptions {
+    /// Fraction of the maximum possible YIQ color delta (`35215`) above which a
+    /// pixel is considered different (default: `0.1`, matching `pixelmatch`).
+    pub threshold: f64,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self { threshold: 0.1 }
+    }
+}
+
+/// Result of [`crate::compare_screenshots`]/[`crate::PageEngine::screenshot_diff`].
+#[derive(Debug, Clone)]
+pub struct DiffResult {
+    /// Number of pixels classified as differing (excludes anti-aliasing noise).
+    pub diff_pixels: usize,
+    /// Total pixels compared (`width * height`).
+    pub total_pixels: usize,
+    /// PNG bytes: unchanged pixels dimmed to grayscale, differing pixels solid red.
+    pub diff_image: Vec<u8>,
+}
+
+/// Options for [`crate::PageEngine::print_to_pdf`], modeled on the WebDriver print
+/// parameters (paper size in inches, margins in inches, orientation, scale, and an
+/// optional page subset).
+#[derive(Debug, Clone)]
+pub struct PdfOptions {
+    /// Paper width in inches (default: 8.5, US Letter).
+    pub paper_width: f64,
+    /// Paper height in inches (default: 11.0, US Letter).
+    pub paper_height: f64,
+    /// Top margin in inches (default: 1.0).
+    pub margin_top: f64,
+    /// Bottom margin in inches (default: 1.0).
+    pub margin_bottom: f64,
+    /// Left margin in inches (default: 1.0).
+    pub margin_left: f64,
+    /// Right margin in inches (default: 1.0).
+    pub margin_right: f64,
+    pub orientation: Orientation,
+    /// Scale factor applied to the rendered content (default: 1.0).
+    pub scale: f64,
+    /// Whether to render CSS backgrounds (default: false).
+    pub background: bool,
+    /// Restrict output to these 1-indexed pages. `None` prints everything.
+    pub page_ranges: Option<Vec<PageRange>>,
+    /// Read the page's own `@page { size: ... }` CSS rule and use it in place of
+    /// `paper_width`/`paper_height`/`orientation`, if one's declared (default: false).
+    /// See [`crate::PageEngine::print_to_pdf`] for the size syntax this understands.
+    pub prefer_css_page_size: bool,
+}
+
+impl PdfOptions {
+    /// Whether `page` (1-indexed) should be included, per `page_ranges`.
+    pub fn includes_page(&self, page: u32) -> bool {
+        match &self.page_ranges {
+            None => true,
+            Some(ranges) => ranges.iter().any(|r| r.contains(page)),
+        }
+    }
+
+    /// Set `paper_width`/`paper_height` from a named paper size instead of raw inches.
+    pub fn with_paper_size(mut self, size: PaperSize) -> Self {
+        let (width, height) = size.dimensions_inches();
+        self.paper_width = width;
+        self.paper_height = height;
+        self
+    }
+}
+
+/// A named paper size, for [`PdfOptions::with_paper_size`] -- an alternative to
+/// setting `paper_width`/`paper_height` directly in inches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaperSize {
+    Letter,
+    Legal,
+    Tabloid,
+    A3,
+    A4,
+    A5,
+}
+
+impl PaperSize {
+    /// Width/height in inches, in portrait orientation.
+    pub fn dimensions_inches(&self) -> (f64, f64) {
+        match self {
+            PaperSize::Letter => (8.5, 11.0),
+            PaperSize::Legal => (8.5, 14.0),
+            PaperSize::Tabloid => (11.0, 17.0),
+            PaperSize::A3 => (11.69, 16.54),
+            PaperSize::A4 => (8.27, 11.69),
+            PaperSize::A5 => (5.83, 8.27),
+        }
+    }
+}
+
+impl Default for PdfOptions {
+    fn default() -> Self {
+        Self {
+            paper_width: 8.5,
+            paper_height: 11.0,
+            margin_top: 1.0,
+            margin_bottom: 1.0,
+            margin_left: 1.0,
+            margin_right: 1.0,
+            orientation: Orientation::Portrait,
+            scale: 1.0,
+            background: false,
+            page_ranges: None,
+            prefer_css_page_size: false,
+        }
+    }
+}
+
+/// An event pushed to a [`crate::Page::events`]/[`crate::PageEngine::subscribe`]
+/// receiver as it happens, rather than polled like
+/// [`crate::PageEngine::console_messages`]/[`crate::PageEngine::network_requests`].
+/// See [`event_kinds`] for the bitset that selects which of these a subscriber
+/// receives.
+#[derive(Debug, Clone)]
+pub enum PageEvent {
+    /// A JS console message, mirroring [`ConsoleMessage`].
+    Console { level: String, message: String },
+    /// A request was observed via the WebView delegate's resource-load notification.
+    NetworkRequestStarted {
+        method: String,
+        url: String,
+        is_main_frame: bool,
+    },
+    /// A request this engine fulfilled itself (via
+    /// [`crate::PageEngine::on_request`]/[`crate::PageEngine::add_route`]) finished.
+    /// Like [`NetworkRequest::status`], there is no hook to learn when a request
+    /// Servo's own network stack handled finishes, so this only ever fires for
+    /// self-fulfilled requests.
+    NetworkRequestFinished { url: String, status: u16 },
+    /// A navigation was requested, via [`crate::PageEngine::open`] or the page's own
+    /// script (a link click, `location.href`, etc. -- see [`NavigationCommitted`](Self::NavigationCommitted)).
+    NavigationStarted { url: String },
+    /// The load event fired (`LoadStatus::Complete`). Not a true document-commit hook
+    /// -- see the doc comment on [`crate::PageEngine::add_init_script`].
+    NavigationCommitted { url: String },
+    /// A new page (tab or popup) was created.
+    PageOpened { page_id: u32 },
+    /// A page was closed.
+    PageClosed { page_id: u32 },
+}
+
+impl PageEvent {
+    /// The single [`event_kinds`] bit this event belongs to, for filtering against the
+    /// bitset passed to [`crate::PageEngine::subscribe`].
+    pub fn kind(&self) -> u32 {
+        match self {
+            PageEvent::Console { .. } => event_kinds::CONSOLE,
+            PageEvent::NetworkRequestStarted { .. } => event_kinds::NETWORK_REQUEST_STARTED,
+            PageEvent::NetworkRequestFinished { .. } => event_kinds::NETWORK_REQUEST_FINISHED,
+            PageEvent::NavigationStarted { .. } => event_kinds::NAVIGATION_STARTED,
+            PageEvent::NavigationCommitted { .. } => event_kinds::NAVIGATION_COMMITTED,
+            PageEvent::PageOpened { .. } => event_kinds::PAGE_OPENED,
+            PageEvent::PageClosed { .. } => event_kinds::PAGE_CLOSED,
+        }
+    }
+}
+
+/// Bit flags selecting which [`PageEvent`] variants a [`crate::PageEngine::subscribe`]
+/// call should receive.
+pub mod event_kinds {
+    pub const CONSOLE: u32 = 1 << 0;
+    pub const NETWORK_REQUEST_STARTED: u32 = 1 << 1;
+    pub const NETWORK_REQUEST_FINISHED: u32 = 1 << 2;
+    pub const NAVIGATION_STARTED: u32 = 1 << 3;
+    pub const NAVIGATION_COMMITTED: u32 = 1 << 4;
+    pub const PAGE_OPENED: u32 = 1 << 5;
+    pub const PAGE_CLOSED: u32 = 1 << 6;
+    /// Every event kind.
+    pub const ALL: u32 = CONSOLE
+        | NETWORK_REQUEST_STARTED
+        | NETWORK_REQUEST_FINISHED
+        | NAVIGATION_STARTED
+        | NAVIGATION_COMMITTED
+        | PAGE_OPENED
+        | PAGE_CLOSED;
+}
+
+/// How [`crate::PageEngine::request_create_new`] (a popup/`window.open` request from
+/// page script) is handled, set via
+/// [`crate::PageEngine::set_popup_policy`]/[`crate::Page::set_popup_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PopupPolicy {
+    /// Drop the request; no WebView is created. The default, matching the prior
+    /// `set_popup_handling(false)` behavior.
+    #[default]
+    Block,
+    /// Create the popup as a new page, buffered for [`crate::PageEngine::popup_pages`]
+    /// to drain, same as the prior `set_popup_handling(true)` behavior.
+    Capture,
+    /// Don't create a second page at all -- navigate the opener's own WebView to the
+    /// popup's URL instead, for sites that `window.open` a link that's really meant to
+    /// replace the current tab.
+    Redirect,
+}
+
+/// An event pushed to a [`crate::Page::popup_events`]/
+/// [`crate::PageEngine::popup_events`] receiver as a popup opens or closes, rather
+/// than polled like [`crate::PageEngine::popup_pages`]. Unlike [`PageEvent`], this
+/// fires only for popups and is not gated by a bitset -- there's only the one kind of
+/// thing to report.
+#[derive(Debug, Clone)]
+pub struct PopupEvent {
+    /// The popup's page ID, or `None` for [`PopupPolicy::Redirect`], where no second
+    /// page is ever created.
+    pub page_id: Option<u32>,
+    /// The popup's URL at the time of the event.
+    pub url: String,
+    /// The opener page's ID, if it's known to have one.
+    pub opener_id: Option<u32>,
+    pub kind: PopupEventKind,
+}
+
+/// Which half of a popup's lifecycle a [`PopupEvent`] reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PopupEventKind {
+    /// A popup was requested and (for [`PopupPolicy::Capture`]) created.
+    Opened,
+    /// A popup's WebView was closed, e.g. via `window.close()`. Only fires for
+    /// [`PopupPolicy::Capture`] popups, which are the only ones that persist as a
+    /// page with a lifecycle to close.
+    Closed,
+}
+
+/// Bit flags controlling [`crate::PageEngine::save_archive`] behavior.
+pub mod archive_flags {
+    /// Strip `<script>` elements and `on*` event handler attributes from the archive.
+    pub const EXCLUDE_JS: u32 = 1 << 0;
+    /// Strip `<style>` content and `<link rel="stylesheet">` references.
+    pub const EXCLUDE_CSS: u32 = 1 << 1;
+    /// Inject a restrictive CSP `<meta>` tag so the archived document cannot phone home.
+    pub const ISOLATE: u32 = 1 << 2;
+    /// Hash fetched bytes and compare them against any `integrity=` attribute. A
+    /// mismatch does not change the archive: the asset is still inlined. The
+    /// `integrity=` attribute is removed from every inlined element whether or not
+    /// this flag is set (the data URI is already pinned, so a stale SRI hash would
+    /// just break rendering).
+    pub const VERIFY_INTEGRITY: u32 = 1 << 3;
+    /// Strip `<img>`, `<source>`, `<video>`, and `<audio>` elements from the archive.
+    pub const EXCLUDE_IMAGES: u32 = 1 << 4;
+    /// Omit the `<!-- Archived from ... -->` comment that otherwise records the
+    /// source URL and capture timestamp at the top of the document.
+    pub const EXCLUDE_SOURCE_COMMENT: u32 = 1 << 5;
+}
+
+/// Bit flags controlling [`crate::PageEngine::find_text`] behavior.
+pub mod find_flags {
+    /// Match case exactly instead of case-insensitively.
+    pub const CASE_SENSITIVE: u32 = 1 << 0;
+    /// Only match whole words, not substrings of a larger word.
+    pub const WHOLE_WORD: u32 = 1 << 1;
+    /// Wrap around to the first/last match when `find_next`/`find_previous` runs out.
+    pub const WRAP: u32 = 1 << 2;
+}
+
+/// A way of locating an element: CSS selector or XPath expression. Accepted by the
+/// `*_by` element-info methods (e.g. [`crate::PageEngine::element_rect_by`]) alongside
+/// the CSS-only `element_rect`/`element_text`/`element_attribute`/`element_html`, for
+/// structural queries CSS can't express (e.g. "the `<td>` following the label
+/// 'Price'").
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locator {
+    Css(String),
+    XPath(String),
+}
+
+impl fmt::Display for Locator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Locator::Css(selector) => write!(f, "css:{selector}"),
+            Locator::XPath(expr) => write!(f, "xpath:{expr}"),
+        }
+    }
+}
+
+/// The position and size of an element, from `getBoundingClientRect()`: viewport-relative
+/// and in CSS pixels, not affected by [`PageOptions::device_scale_factor`].
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ElementRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Rect, text, outer HTML, and attributes of a single element, returned in bulk by
+/// [`crate::PageEngine::query_all`]/[`crate::PageEngine::element_info`] instead of
+/// requiring a separate `eval_js` round-trip per field.
+#[derive(Debug, Clone, Serialize)]
+pub struct ElementInfo {
+    pub rect: ElementRect,
+    pub text: String,
+    pub outer_html: String,
+    pub attributes: HashMap<String, String>,
+}
+
+/// A page's social/semantic metadata, gathered by [`crate::PageEngine::metadata`] in a
+/// single `eval_js` round trip rather than requiring the caller to scrape `<meta>`/
+/// `<link>`/`<script type="application/ld+json">` tags by hand.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PageMetadata {
+    /// `document.title`.
+    pub title: Option<String>,
+    /// `<meta name="description">` content.
+    pub description: Option<String>,
+    /// `<link rel="canonical">` href, resolved to an absolute URL.
+    pub canonical: Option<String>,
+    /// `document.documentElement.lang`.
+    pub language: Option<String>,
+    /// `document.characterSet`.
+    pub charset: Option<String>,
+    /// `<meta property="og:*">` tags, keyed by the part after `og:` (`title`, `type`,
+    /// `image`, `url`, `description`, `site_name`, etc).
+    pub opengraph: HashMap<String, String>,
+    /// `<meta name="twitter:*">` tags, keyed by the part after `twitter:`.
+    pub twitter: HashMap<String, String>,
+    /// Every `<script type="application/ld+json">` block, parsed as JSON. Blocks that
+    /// fail to parse are skipped rather than failing the whole call.
+    pub schema_org: Vec<Value>,
+}
+
+/// A readability-style extraction of a page's main content, independent of
+/// chrome, navigation, ads, and comments. Returned by
+/// [`crate::PageEngine::extract_article`]; also the basis for
+/// [`crate::PageEngine::save_epub`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Article {
+    /// `document.title`, if non-empty.
+    pub title: Option<String>,
+    /// Best-effort byline, read from `[rel="author"]`/`.byline`/`.author`.
+    pub byline: Option<String>,
+    /// Inner HTML of the highest-scoring content subtree, with junk elements
+    /// stripped and `img`/`a` URLs rewritten to absolute.
+    pub content_html: String,
+    /// Plain-text rendering of `content_html` (`innerText` of the same subtree).
+    pub text: String,
+    /// `document.documentElement.lang`, if set.
+    pub lang: Option<String>,
+}
+
 /// A console message captured from the page.
 #[derive(Debug, Clone, Serialize)]
 pub struct ConsoleMessage {
@@ -42,12 +724,252 @@ pub struct ConsoleMessage {
     pub message: String,
 }
 
+/// Classifies a navigation failure detected during [`crate::PageEngine::open`]/
+/// [`crate::PageEngine::reload`]. See [`crate::PageEngine::last_navigation_error`] for
+/// the honest scope of what this crate can detect: there's no net-error hook in this
+/// embedding API, so this only covers the main-frame request when this engine itself
+/// observed and answered it (an `on_request`/`add_route` handler that blocked,
+/// aborted, failed, or fulfilled it with a 4xx/5xx status). A real DNS failure,
+/// connection refusal, or TLS error on a request Servo answers over the actual network
+/// still surfaces as a normal `Complete` load of whatever error page Servo renders,
+/// indistinguishable from a successful load at this layer.
+#[derive(Debug, Clone, Serialize)]
+pub struct NavigationError {
+    /// A short machine-readable code: `"blocked"`, `"aborted"`, `"failed: <reason>"`
+    /// (see [`RequestDecision::Fail`]), or an HTTP status number as a string.
+    pub code: String,
+    pub url: String,
+}
+
+/// An uncaught JS exception or unhandled promise rejection captured from the page,
+/// distinct from [`ConsoleMessage`] -- see [`crate::PageEngine::js_exceptions`].
+#[derive(Debug, Clone, Serialize)]
+pub struct JsException {
+    pub message: String,
+    /// Best-effort stack trace, if the engine reported an `Error` object with one
+    /// (not all thrown values are `Error`s, and Servo doesn't always supply one).
+    pub stack: Option<String>,
+    /// Script URL the exception originated from, if known.
+    pub source_url: Option<String>,
+    /// 1-based source line, if known.
+    pub line: Option<u32>,
+}
+
+/// Device emulation settings applied to the active page via
+/// [`crate::PageEngine::set_emulation`], modeled on chromiumoxide's `Viewport`.
+#[derive(Debug, Clone, Copy)]
+pub struct EmulationSettings {
+    /// Logical (CSS) viewport width in pixels.
+    pub width: u32,
+    /// Logical (CSS) viewport height in pixels.
+    pub height: u32,
+    /// Device scale factor, e.g. `2.0`/`3.0` for retina-resolution output. The
+    /// rendering surface is resized to `width*dpr x height*dpr` physical pixels.
+    pub device_scale_factor: f32,
+    /// Whether `navigator` should report itself as a mobile device.
+    pub is_mobile: bool,
+    /// Whether touch events/`navigator.maxTouchPoints` should be reported as available.
+    pub has_touch: bool,
+}
+
+/// A named device preset bundling [`EmulationSettings`] with the user-agent string
+/// that device would send, applied in one call via [`crate::PageEngine::emulate`].
+/// Modeled on Puppeteer's `KnownDevices` table; see [`Self::iphone_x`], [`Self::pixel_5`],
+/// and [`Self::ipad`] for the presets this crate ships.
+#[derive(Debug, Clone)]
+pub struct DeviceDescriptor {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub device_scale_factor: f32,
+    pub is_mobile: bool,
+    pub has_touch: bool,
+    pub user_agent: String,
+}
+
+impl DeviceDescriptor {
+    /// iPhone X-class preset: 375x812 @ 3x, mobile + touch.
+    pub fn iphone_x() -> Self {
+        Self {
+            name: "iPhone X".to_string(),
+            width: 375,
+            height: 812,
+            device_scale_factor: 3.0,
+            is_mobile: true,
+            has_touch: true,
+            user_agent: "Mozilla/5.0 (iPhone; CPU iPhone OS 16_0 like Mac OS X) \
+                AppleWebKit/605.1.15 (KHTML, like Gecko) Version/16.0 Mobile/15E148 Safari/604.1"
+                .to_string(),
+        }
+    }
+
+    /// Pixel 5-class preset: 393x851 @ 2.75x, mobile + touch.
+    pub fn pixel_5() -> Self {
+        Self {
+            name: "Pixel 5".to_string(),
+            width: 393,
+            height: 851,
+            device_scale_factor: 2.75,
+            is_mobile: true,
+            has_touch: true,
+            user_agent: "Mozilla/5.0 (Linux; Android 13; Pixel 5) AppleWebKit/537.36 \
+                (KHTML, like Gecko) Chrome/120.0.0.0 Mobile Safari/537.36"
+                .to_string(),
+        }
+    }
+
+    /// iPad-class preset: 810x1080 @ 2x, mobile (tablet) + touch.
+    pub fn ipad() -> Self {
+        Self {
+            name: "iPad".to_string(),
+            width: 810,
+            height: 1080,
+            device_scale_factor: 2.0,
+            is_mobile: true,
+            has_touch: true,
+            user_agent: "Mozilla/5.0 (iPad; CPU OS 16_0 like Mac OS X) AppleWebKit/605.1.15 \
+                (KHTML, like Gecko) Version/16.0 Mobile/15E148 Safari/604.1"
+                .to_string(),
+        }
+    }
+}
+
+/// Media-query emulation applied via [`crate::PageEngine::emulate_media`]: `media`
+/// overrides the emulated media type (`"screen"`/`"print"`), and `features` are
+/// `(name, value)` pairs fed to an overridden `window.matchMedia`, e.g.
+/// `("prefers-color-scheme", "dark")`. See [`crate::PageEngine::emulate_media`] for
+/// why this reaches `matchMedia()` calls but not `@media` blocks in stylesheets.
+#[derive(Debug, Clone, Default)]
+pub struct MediaEmulation {
+    pub media: Option<String>,
+    pub features: Vec<(String, String)>,
+}
+
+/// A file to inject into an `<input type="file">` element via
+/// [`crate::PageEngine::set_input_files`].
+#[derive(Debug, Clone)]
+pub struct InputFile {
+    pub name: String,
+    pub mime_type: String,
+    pub data: Vec<u8>,
+}
+
 /// A network request observed during page loading.
 #[derive(Debug, Clone, Serialize)]
 pub struct NetworkRequest {
+    /// Opaque, per-page-session-unique id, assigned in observation order. Stable handle
+    /// for [`crate::PageEngine::response_body`], since `url` alone can't disambiguate
+    /// repeated requests to the same endpoint.
+    pub request_id: String,
     pub method: String,
     pub url: String,
     pub is_main_frame: bool,
+    /// When the request was observed, as an ISO-8601 / RFC 3339 UTC timestamp.
+    pub started_at: String,
+    /// Best-effort MIME type inferred from the URL, e.g. for [`crate::PageEngine::har`]
+    /// entries. Falls back to `"application/octet-stream"` when it can't be guessed.
+    pub mime_type: String,
+    /// Best-effort resource type inferred from the URL/main-frame-ness: `document`,
+    /// `stylesheet`, `image`, `script`, `xhr`, or `font`. The same classification
+    /// [`crate::PageEngine::add_route`] scopes rules by.
+    pub resource_type: String,
+    /// Response status code, if this engine itself fulfilled the request via
+    /// [`crate::PageEngine::on_request`] or [`crate::PageEngine::add_route`]. `None` for
+    /// requests Servo answered over the real network, since the embedding API gives no
+    /// hook to observe those responses.
+    pub status: Option<u16>,
+    /// Response headers, populated under the same conditions as `status`.
+    #[serde(default)]
+    pub response_headers: HashMap<String, String>,
+    /// Response body, populated under the same conditions as `status`, gated by
+    /// [`PageOptions::capture_bodies`] and capped by
+    /// [`PageOptions::max_body_capture_bytes`].
+    #[serde(default)]
+    pub body: Option<Vec<u8>>,
+    /// `true` if `body` was cut off at [`PageOptions::max_body_capture_bytes`] rather
+    /// than holding the complete response. Always `false` when `body` is `None`.
+    #[serde(default)]
+    pub was_truncated: bool,
+    /// Whether the response came from a cache, populated under the same conditions as
+    /// `status`. Always `Some(false)` there: a self-fulfilled response is synthesized
+    /// fresh by [`crate::PageEngine::on_request`]/[`crate::PageEngine::add_route`]
+    /// every time, never served from a cache this crate knows about.
+    #[serde(default)]
+    pub from_cache: Option<bool>,
+    /// Response body size in bytes, populated under the same conditions as `status`
+    /// -- unlike `body`, not gated by [`PageOptions::capture_bodies`], since the
+    /// length is free to record even when the bytes themselves aren't kept. There's no
+    /// transfer-encoding distinct from this to report (see `from_cache`), so this
+    /// doubles as both "encoded" and "decoded" length.
+    #[serde(default)]
+    pub encoded_data_length: Option<u64>,
+    /// Elapsed time between the request being observed and its response being
+    /// recorded, populated under the same conditions as `status`. Used as the HAR
+    /// `time`/`wait` value in [`crate::PageEngine::har`] entries.
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
+    /// When the request was first observed, for computing `duration_ms` once the
+    /// response (if any) comes in. Not serialized: it's process-local bookkeeping,
+    /// not part of the public capture record.
+    #[serde(skip)]
+    pub started_instant: Option<std::time::Instant>,
+}
+
+/// Result of [`crate::PageEngine::response_body`]. Splits the raw capture out of
+/// [`NetworkRequest`] so callers extracting a payload don't have to re-derive
+/// content-type/truncation bookkeeping themselves.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResponseBody {
+    /// Best-effort MIME type, copied from [`NetworkRequest::mime_type`].
+    pub content_type: String,
+    /// See [`NetworkRequest::was_truncated`].
+    pub was_truncated: bool,
+    /// Body bytes, base64-encoded -- consistent with how this crate already hands
+    /// binary payloads (e.g. [`InputFile`], route `Fulfill` bodies) across JSON-shaped
+    /// boundaries rather than assuming UTF-8.
+    pub data_base64: String,
+}
+
+/// A request that was matched and resolved by [`crate::PageEngine::add_route`] or
+/// [`crate::PageEngine::on_request`], recorded separately from the full
+/// [`NetworkRequest`] log so callers can audit which requests an interception rule
+/// actually fired on (e.g. to verify an ad-blocking rule works) without draining
+/// `network_requests()`, which records every request regardless of whether anything
+/// intercepted it.
+///
+/// There's no hook in this embedding API to pause an in-flight request and resolve it
+/// later from a different call — see [`crate::PageEngine::add_route`] — so, unlike
+/// Chrome's Fetch domain, the decision here is always made synchronously by a rule or
+/// callback already registered before the request arrives; this log is purely
+/// after-the-fact visibility into those decisions.
+#[derive(Debug, Clone, Serialize)]
+pub struct InterceptedRequest {
+    pub method: String,
+    pub url: String,
+    pub is_main_frame: bool,
+    /// `"block"`/`"abort"`, `"redirect"`, `"fulfill"`, or `"fail"`.
+    pub action: String,
+    /// The reason given to [`RequestDecision::Fail`], if `action` is `"fail"`.
+    pub detail: Option<String>,
+}
+
+/// A handle to a single DOM element, returned by [`crate::PageEngine::find`] /
+/// [`crate::PageEngine::find_all`]. Unlike the selector-based `element_*` methods, a
+/// handle stays bound to the exact element it was resolved from rather than
+/// re-querying `selector` and picking whichever element matches first — useful once
+/// `find_all` has disambiguated several elements matching the same selector.
+///
+/// Internally this is done by stamping a unique `data-scraper-handle` attribute onto
+/// the element when the handle is created; `handle_*` operations re-query by that
+/// attribute rather than by `selector`, which is kept only for error messages. The
+/// marker doesn't survive a navigation or reload, so a handle from before
+/// `open`/`reload`/`go_back`/`go_forward` is no longer valid afterwards and `handle_*`
+/// calls on it fail with [`PageError::SelectorNotFound`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElementHandle {
+    pub id: u32,
+    /// The selector this handle was resolved from, kept for diagnostics only.
+    pub selector: String,
 }
 
 /// Errors that can occur during page operations.
@@ -63,12 +985,30 @@ pub enum PageError {
     JsError(String),
     /// Screenshot capture failed.
     ScreenshotFailed(String),
+    /// PDF rendering or encoding failed.
+    PdfFailed(String),
     /// Internal channel was closed (FFI wrapper).
     ChannelClosed,
     /// No page is open (WebView not created).
     NoPage,
     /// CSS selector matched nothing.
     SelectorNotFound(String),
+    /// [`crate::PageEngine::response_body`] was given a `request_id` that doesn't match
+    /// any captured [`NetworkRequest`].
+    ResponseBodyNotFound(String),
+    /// [`crate::PageEngine::discard_page`] was called on the currently active page --
+    /// call [`crate::PageEngine::switch_to`] another page first.
+    CannotDiscardActivePage,
+    /// The element matched by a selector exists but has zero width or height (e.g. it
+    /// is `display: none` or off-screen in a way the layout never resolves), so it
+    /// cannot receive a synthetic click or keyboard focus.
+    ElementNotInteractable(String),
+    /// [`crate::PageEngine::open`]/[`crate::PageEngine::reload`] classified the
+    /// navigation itself as a failure rather than a successful load, instead of
+    /// letting it through as a misleading "error page" title. See
+    /// [`NavigationError`]/[`crate::PageEngine::last_navigation_error`] for the scope
+    /// of what this crate can actually detect.
+    Navigation { code: String, url: String },
 }
 
 impl fmt::Display for PageError {
@@ -79,9 +1019,22 @@ impl fmt::Display for PageError {
             PageError::Timeout => write!(f, "timed out"),
             PageError::JsError(msg) => write!(f, "JavaScript error: {msg}"),
             PageError::ScreenshotFailed(msg) => write!(f, "screenshot failed: {msg}"),
+            PageError::PdfFailed(msg) => write!(f, "PDF export failed: {msg}"),
             PageError::ChannelClosed => write!(f, "internal channel closed"),
             PageError::NoPage => write!(f, "no page open"),
             PageError::SelectorNotFound(sel) => write!(f, "selector not found: {sel}"),
+            PageError::ResponseBodyNotFound(id) => {
+                write!(f, "no response body captured for request id: {id}")
+            }
+            PageError::CannotDiscardActivePage => {
+                write!(f, "cannot discard the active page -- switch_to another page first")
+            }
+            PageError::Navigation { code, url } => {
+                write!(f, "navigation to {url} failed: {code}")
+            }
+            PageError::ElementNotInteractable(sel) => {
+                write!(f, "element matched by selector is not interactable (zero size): {sel}")
+            }
         }
     }
 }
@@ -12,9 +12,12 @@
 //! `page.close()` first to reset state (drop the WebView), then `page.open()`
 //! as needed.
 
-use servo_scraper::{Page, PageError, PageOptions};
-use std::sync::OnceLock;
-use std::time::Instant;
+use servo_scraper::{
+    archive_flags, find_flags, Action, Cookie, DeviceDescriptor, DialogKind, EmulationSettings,
+    Locator, Page, PageError, PageOptions, PdfOptions, PointerButton, PopupEventKind, PopupPolicy,
+};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 // ---------------------------------------------------------------------------
 // Test HTML constants
@@ -67,6 +70,52 @@ const TALL_HTML: &str = "\
 <div style=\"height:3000px;background:linear-gradient(red,blue);\">Tall content</div>\
 </body></html>";
 
+const OFFSCREEN_HTML: &str = "\
+<html><head><title>Offscreen Page</title></head><body>\
+<div style=\"height:3000px;\">Spacer</div>\
+<div id=\"target\" style=\"width:100px;height:100px;background:blue;\">Target</div>\
+</body></html>";
+
+const FIND_HTML: &str = "\
+<html><head><title>Find Page</title></head><body>\
+<p id=\"a\">the quick brown fox</p>\
+<p id=\"b\">jumps over THE lazy dog</p>\
+<p id=\"c\">the end</p>\
+</body></html>";
+
+const CONTINUOUS_FETCH_HTML: &str = "\
+<html><head><title>Continuous Fetch Page</title></head><body>\
+<script>\
+setInterval(function() {\
+  fetch('data:text/plain,tick');\
+}, 100);\
+</script>\
+</body></html>";
+
+const ARCHIVE_HTML: &str = "\
+<html><head><title>Archive Page</title>\
+<style>body { color: red; }</style>\
+</head><body>\
+<script>window.archived = true;</script>\
+<img src=\"data:image/png;base64,iVBORw0KGgo=\" id=\"pic\" />\
+</body></html>";
+
+const NOSCRIPT_HTML: &str = "\
+<html><head><title>Noscript Page</title></head><body>\
+<script>document.title = 'Scripted';</script>\
+<button id=\"btn\" onclick=\"alert(1)\">Click</button>\
+<noscript><p id=\"fallback\">No JS here</p></noscript>\
+</body></html>";
+
+const LIST_HTML: &str = "\
+<html><head><title>List Page</title></head><body>\
+<ul>\
+<li class=\"item\">One</li>\
+<li class=\"item\">Two</li>\
+<li class=\"item\">Three</li>\
+</ul>\
+</body></html>";
+
 const CONDITION_HTML: &str = "\
 <html><head><title>Condition Page</title></head><body>\
 <script>\
@@ -90,6 +139,15 @@ fn page() -> &'static Page {
             wait: 0.5,
             fullpage: false,
             user_agent: None,
+            request_rules: Vec::new(),
+            cookies: Vec::new(),
+            extra_headers: Vec::new(),
+            basic_auth: None,
+            init_scripts: Vec::new(),
+            capture_bodies: true,
+            max_body_capture_bytes: 2 * 1024 * 1024,
+            device_scale_factor: 1.0,
+            max_live_pages: None,
         };
         Page::new(opts).expect("Page init failed")
     })
@@ -209,6 +267,32 @@ fn test_html_before_open() {
     }
 }
 
+#[test]
+fn test_html_static_expands_noscript_and_strips_scripting() {
+    reset_and_open(NOSCRIPT_HTML);
+
+    let html = page().html_static().expect("html_static() failed");
+    assert!(
+        html.contains("No JS here"),
+        "noscript content should be spliced in: {html}"
+    );
+    assert!(!html.contains("<noscript"), "noscript tag should be gone");
+    assert!(!html.contains("<script"), "script tag should be stripped");
+    assert!(
+        !html.contains("onclick"),
+        "event handler should be stripped"
+    );
+}
+
+#[test]
+fn test_html_static_before_open() {
+    reset();
+    match page().html_static() {
+        Err(PageError::NoPage) => {}
+        other => panic!("expected NoPage, got: {other:?}"),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Group 4: JavaScript Evaluation
 // ---------------------------------------------------------------------------
@@ -256,6 +340,28 @@ fn test_evaluate_before_open() {
     }
 }
 
+#[test]
+fn test_evaluate_isolated_basic_expression() {
+    reset_and_open(BASIC_HTML);
+
+    let result = page().evaluate_isolated("document.title").unwrap();
+    assert_eq!(result, "\"Test Page\"");
+}
+
+#[test]
+fn test_evaluate_isolated_survives_corrupted_globals() {
+    reset_and_open(BASIC_HTML);
+    let p = page();
+
+    p.evaluate("JSON.stringify = function() { throw new Error('pwned'); }; 1")
+        .unwrap();
+
+    let result = p
+        .evaluate_isolated("JSON.stringify({ok: true})")
+        .expect("isolated eval should use its own pristine JSON, not the page's corrupted one");
+    assert!(result.contains("\"ok\""), "unexpected result: {result}");
+}
+
 // ---------------------------------------------------------------------------
 // Group 5: Screenshots
 // ---------------------------------------------------------------------------
@@ -307,6 +413,72 @@ fn test_screenshot_fullpage_before_open() {
     }
 }
 
+#[test]
+fn test_screenshot_clip_is_smaller_than_viewport() {
+    reset_and_open(BASIC_HTML);
+
+    let p = page();
+    let viewport_png = p.screenshot().unwrap();
+    let clip_png = p.screenshot_clip(0, 0, 100, 100).unwrap();
+
+    assert_eq!(&clip_png[..4], &PNG_MAGIC);
+    assert!(
+        clip_png.len() < viewport_png.len(),
+        "clip ({}) should be smaller than viewport ({})",
+        clip_png.len(),
+        viewport_png.len()
+    );
+}
+
+#[test]
+fn test_screenshot_clip_region_outside_viewport() {
+    reset_and_open(BASIC_HTML);
+
+    match page().screenshot_clip(100_000, 100_000, 10, 10) {
+        Err(PageError::ScreenshotFailed(_)) => {}
+        other => panic!("expected ScreenshotFailed, got: {other:?}"),
+    }
+}
+
+#[test]
+fn test_screenshot_element() {
+    reset_and_open(BASIC_HTML);
+
+    let p = page();
+    let viewport_png = p.screenshot().unwrap();
+    let element_png = p.screenshot_element("#heading").unwrap();
+
+    assert_eq!(&element_png[..4], &PNG_MAGIC);
+    assert!(
+        element_png.len() < viewport_png.len(),
+        "element screenshot ({}) should be smaller than viewport ({})",
+        element_png.len(),
+        viewport_png.len()
+    );
+}
+
+#[test]
+fn test_screenshot_element_not_found() {
+    reset_and_open(BASIC_HTML);
+
+    match page().screenshot_element("#nonexistent") {
+        Err(PageError::SelectorNotFound(sel)) => assert_eq!(sel, "#nonexistent"),
+        other => panic!("expected SelectorNotFound, got: {other:?}"),
+    }
+}
+
+#[test]
+fn test_screenshot_element_scrolls_offscreen_target_into_view() {
+    reset_and_open(OFFSCREEN_HTML);
+
+    // #target starts 3000px below the fold, well outside the default viewport; a
+    // non-empty capture proves it was scrolled into view before cropping.
+    let png = page()
+        .screenshot_element("#target")
+        .expect("screenshot_element failed");
+    assert_eq!(&png[..4], &PNG_MAGIC);
+}
+
 // ---------------------------------------------------------------------------
 // Group 6: Console Messages
 // ---------------------------------------------------------------------------
@@ -381,6 +553,18 @@ fn test_network_requests_drain() {
     assert!(second.is_empty(), "second drain should be empty");
 }
 
+#[test]
+fn test_network_requests_resource_type() {
+    reset_and_open(BASIC_HTML);
+
+    let requests = page().network_requests();
+    let main_frame = requests
+        .iter()
+        .find(|r| r.is_main_frame)
+        .expect("expected a main-frame request");
+    assert_eq!(main_frame.resource_type, "document");
+}
+
 // ---------------------------------------------------------------------------
 // Group 8: Wait Mechanisms
 // ---------------------------------------------------------------------------
@@ -413,6 +597,67 @@ fn test_wait_for_selector_timeout() {
     }
 }
 
+const DISAPPEARING_HTML: &str = "\
+<html><head><title>Disappearing Page</title></head><body>\
+<div id=\"loading\">Loading...</div>\
+<script>\
+setTimeout(function() {\
+  var el = document.getElementById('loading');\
+  el.parentNode.removeChild(el);\
+}, 500);\
+</script>\
+</body></html>";
+
+#[test]
+fn test_wait_for_selector_gone_delayed() {
+    reset_and_open(DISAPPEARING_HTML);
+
+    page()
+        .wait_for_selector("#loading", 5)
+        .expect("#loading should be present immediately");
+    page()
+        .wait_for_selector_gone("#loading", 10)
+        .expect("#loading should disappear after the setTimeout removes it");
+}
+
+#[test]
+fn test_wait_for_selector_gone_already_absent() {
+    reset_and_open(BASIC_HTML);
+
+    page()
+        .wait_for_selector_gone("#nonexistent", 5)
+        .expect("a selector that never matched should report gone immediately");
+}
+
+#[test]
+fn test_wait_for_selector_gone_timeout() {
+    reset_and_open(BASIC_HTML);
+
+    match page().wait_for_selector_gone("#heading", 1) {
+        Err(PageError::Timeout) => {}
+        other => panic!("expected Timeout, got: {other:?}"),
+    }
+}
+
+#[test]
+fn test_wait_for_network_idle_settles_after_load() {
+    reset_and_open(BASIC_HTML);
+
+    page()
+        .wait_for_network_idle(100, 5)
+        .expect("network should go idle shortly after a static page loads");
+}
+
+#[test]
+fn test_wait_for_network_idle_timeout_under_continuous_requests() {
+    reset_and_open(CONTINUOUS_FETCH_HTML);
+
+    match page().wait_for_network_idle(500, 2) {
+        Err(PageError::Timeout) => {}
+        other => panic!("expected Timeout, got: {other:?}"),
+    }
+}
+
 #[test]
 fn test_wait_for_condition() {
     reset_and_open(CONDITION_HTML);
@@ -552,6 +797,31 @@ fn test_click_selector_not_found() {
     }
 }
 
+#[test]
+fn test_focus() {
+    reset_and_open(FORM_HTML);
+    let p = page();
+
+    p.focus("#name-input").expect("focus failed");
+
+    let active_id = p
+        .evaluate("document.activeElement.id")
+        .expect("evaluate failed");
+    assert_eq!(active_id, "\"name-input\"");
+}
+
+#[test]
+fn test_focus_not_found() {
+    reset_and_open(BASIC_HTML);
+
+    match page().focus("#nonexistent") {
+        Err(PageError::SelectorNotFound(sel)) => {
+            assert_eq!(sel, "#nonexistent");
+        }
+        other => panic!("expected SelectorNotFound, got: {other:?}"),
+    }
+}
+
 #[test]
 fn test_type_text() {
     reset_and_open(FORM_HTML);
@@ -616,13 +886,13 @@ fn test_get_cookies() {
 }
 
 #[test]
-fn test_set_cookie() {
+fn test_set_cookie_raw() {
     reset_and_open(BASIC_HTML);
 
     // Should not error even on data: origin (cookie just won't persist)
     page()
-        .set_cookie("test=value; path=/")
-        .expect("set_cookie failed");
+        .set_cookie_raw("test=value; path=/")
+        .expect("set_cookie_raw failed");
     // TODO: Real cookie persistence tests need an HTTP server
 }
 
@@ -633,6 +903,56 @@ fn test_clear_cookies() {
     page().clear_cookies().expect("clear_cookies failed");
 }
 
+fn test_cookie(name: &str) -> Cookie {
+    Cookie {
+        name: name.to_string(),
+        value: "value".to_string(),
+        domain: None,
+        path: None,
+        expires: None,
+        secure: false,
+        http_only: false,
+        same_site: None,
+    }
+}
+
+#[test]
+fn test_set_cookie_typed() {
+    reset_and_open(BASIC_HTML);
+
+    page()
+        .set_cookie(&test_cookie("typed"))
+        .expect("set_cookie failed");
+    let cookies = page().cookies().expect("cookies failed");
+    assert!(
+        cookies.iter().any(|c| c.name == "typed"),
+        "expected 'typed' cookie: {cookies:?}"
+    );
+    page().clear_cookies().expect("clear_cookies failed");
+}
+
+#[test]
+fn test_set_cookie_typed_rejects_http_only() {
+    reset_and_open(BASIC_HTML);
+
+    let mut cookie = test_cookie("blocked");
+    cookie.http_only = true;
+    assert!(page().set_cookie(&cookie).is_err());
+}
+
+#[test]
+fn test_set_cookies_batch() {
+    reset_and_open(BASIC_HTML);
+
+    page()
+        .set_cookies(&[test_cookie("batch_a"), test_cookie("batch_b")])
+        .expect("set_cookies failed");
+    let cookies = page().cookies().expect("cookies failed");
+    assert!(cookies.iter().any(|c| c.name == "batch_a"));
+    assert!(cookies.iter().any(|c| c.name == "batch_b"));
+    page().clear_cookies().expect("clear_cookies failed");
+}
+
 // ---------------------------------------------------------------------------
 // Group 13: Request Interception
 // ---------------------------------------------------------------------------
@@ -702,6 +1022,51 @@ fn test_element_text_not_found() {
     }
 }
 
+#[test]
+fn test_element_text_by_xpath() {
+    reset_and_open(BASIC_HTML);
+
+    let text = page()
+        .element_text_by(Locator::XPath("//h1".to_string()))
+        .expect("element_text_by failed");
+    assert_eq!(text, "Hello World");
+}
+
+#[test]
+fn test_element_rect_by_css_matches_element_rect() {
+    reset_and_open(BASIC_HTML);
+    let p = page();
+
+    let by_css = p
+        .element_rect_by(Locator::Css("#heading".to_string()))
+        .expect("element_rect_by failed");
+    let direct = p.element_rect("#heading").expect("element_rect failed");
+    assert_eq!(by_css.x, direct.x);
+    assert_eq!(by_css.width, direct.width);
+}
+
+#[test]
+fn test_element_attribute_by_xpath() {
+    reset_and_open(BASIC_HTML);
+
+    let class = page()
+        .element_attribute_by(Locator::XPath("//h1".to_string()), "class")
+        .expect("element_attribute_by failed");
+    assert_eq!(class, Some("main".to_string()));
+}
+
+#[test]
+fn test_element_html_by_xpath_not_found() {
+    reset_and_open(BASIC_HTML);
+
+    match page().element_html_by(Locator::XPath("//nonexistent".to_string())) {
+        Err(PageError::SelectorNotFound(locator)) => {
+            assert_eq!(locator, "xpath://nonexistent");
+        }
+        other => panic!("expected SelectorNotFound, got: {other:?}"),
+    }
+}
+
 #[test]
 fn test_element_attribute_exists() {
     reset_and_open(BASIC_HTML);
@@ -789,7 +1154,7 @@ fn test_close_then_operations_fail() {
     assert!(matches!(p.key_press("Enter"), Err(PageError::NoPage)));
     assert!(matches!(p.mouse_move(0.0, 0.0), Err(PageError::NoPage)));
     assert!(matches!(p.get_cookies(), Err(PageError::NoPage)));
-    assert!(matches!(p.set_cookie("a=b"), Err(PageError::NoPage)));
+    assert!(matches!(p.set_cookie_raw("a=b"), Err(PageError::NoPage)));
     assert!(matches!(p.clear_cookies(), Err(PageError::NoPage)));
     assert!(matches!(p.element_rect("h1"), Err(PageError::NoPage)));
     assert!(matches!(p.element_text("h1"), Err(PageError::NoPage)));
@@ -866,7 +1231,7 @@ fn test_all_methods_fail_before_open() {
     assert!(matches!(p.key_press("Enter"), Err(PageError::NoPage)));
     assert!(matches!(p.mouse_move(0.0, 0.0), Err(PageError::NoPage)));
     assert!(matches!(p.get_cookies(), Err(PageError::NoPage)));
-    assert!(matches!(p.set_cookie("a=b"), Err(PageError::NoPage)));
+    assert!(matches!(p.set_cookie_raw("a=b"), Err(PageError::NoPage)));
     assert!(matches!(p.clear_cookies(), Err(PageError::NoPage)));
     assert!(matches!(p.element_rect("h1"), Err(PageError::NoPage)));
     assert!(matches!(p.element_text("h1"), Err(PageError::NoPage)));
@@ -898,3 +1263,1391 @@ fn test_all_methods_fail_before_open() {
     let reqs = p.network_requests();
     assert!(reqs.is_empty());
 }
+
+// ---------------------------------------------------------------------------
+// Group 17: Typed Action Sequences
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_perform_action_sequence_click() {
+    reset_and_open(FORM_HTML);
+    let p = page();
+
+    let rect = p.element_rect("#submit-btn").expect("element_rect failed");
+    let cx = (rect.x + rect.width / 2.0) as f32;
+    let cy = (rect.y + rect.height / 2.0) as f32;
+
+    p.perform_action_sequence(vec![
+        Action::MoveTo {
+            x: cx,
+            y: cy,
+            duration: Duration::from_millis(50),
+        },
+        Action::MouseDown(PointerButton::Left),
+        Action::MouseUp(PointerButton::Left),
+    ])
+    .expect("perform_action_sequence failed");
+    p.wait(0.3);
+
+    let result = p.element_text("#result").unwrap();
+    assert_eq!(result, "clicked");
+}
+
+#[test]
+fn test_perform_action_sequence_keys_and_pause() {
+    reset_and_open(FORM_HTML);
+    let p = page();
+
+    p.click_selector("#name-input").expect("click input failed");
+    p.perform_action_sequence(vec![
+        Action::KeyDown("h".to_string()),
+        Action::KeyUp("h".to_string()),
+        Action::Pause(Duration::from_millis(50)),
+        Action::KeyDown("i".to_string()),
+        Action::KeyUp("i".to_string()),
+    ])
+    .expect("perform_action_sequence failed");
+    p.wait(0.2);
+
+    let value = p
+        .evaluate("document.getElementById('name-input').value")
+        .unwrap();
+    assert!(
+        value.contains("hi"),
+        "input value should contain 'hi': {value}"
+    );
+}
+
+#[test]
+fn test_actions_builder_click() {
+    reset_and_open(FORM_HTML);
+    let p = page();
+
+    let rect = p.element_rect("#submit-btn").expect("element_rect failed");
+    let cx = (rect.x + rect.width / 2.0) as f32;
+    let cy = (rect.y + rect.height / 2.0) as f32;
+
+    p.actions()
+        .pointer_move(cx, cy, Duration::from_millis(50))
+        .pointer_down(PointerButton::Left)
+        .pointer_up(PointerButton::Left)
+        .perform()
+        .expect("actions().perform() failed");
+    p.wait(0.3);
+
+    let result = p.element_text("#result").unwrap();
+    assert_eq!(result, "clicked");
+}
+
+#[test]
+fn test_type_text_selector() {
+    reset_and_open(FORM_HTML);
+    let p = page();
+
+    p.type_text_selector("#name-input", "hello")
+        .expect("type_text_selector failed");
+    p.wait(0.2);
+
+    let value = p
+        .evaluate("document.getElementById('name-input').value")
+        .unwrap();
+    assert!(
+        value.contains("hello"),
+        "input value should contain 'hello': {value}"
+    );
+}
+
+#[test]
+fn test_perform_actions_json_click_via_selector_origin() {
+    reset_and_open(FORM_HTML);
+    let p = page();
+
+    let json = r#"[
+        {
+            "type": "pointer",
+            "actions": [
+                { "type": "pointerMove", "x": 0, "y": 0, "origin": "#submit-btn", "duration": 50 },
+                { "type": "pointerDown", "button": 0 },
+                { "type": "pointerUp", "button": 0 }
+            ]
+        }
+    ]"#;
+    p.perform_actions(json).expect("perform_actions failed");
+    p.wait(0.3);
+
+    let result = p.element_text("#result").unwrap();
+    assert_eq!(result, "clicked");
+}
+
+#[test]
+fn test_perform_actions_json_releases_held_button_after_payload() {
+    reset_and_open(FORM_HTML);
+    let p = page();
+
+    // A payload that ends mid-gesture (pointerDown with no matching pointerUp)
+    // must still leave the button released once perform_actions returns, rather
+    // than leaving the page thinking the button is still held.
+    let json = r#"[
+        {
+            "type": "pointer",
+            "actions": [
+                { "type": "pointerMove", "x": 0, "y": 0, "origin": "#submit-btn", "duration": 0 },
+                { "type": "pointerDown", "button": 0 }
+            ]
+        }
+    ]"#;
+    p.perform_actions(json)
+        .expect("perform_actions with dangling pointerDown failed");
+    p.wait(0.3);
+
+    // The dangling pointerDown is auto-released as a MouseButton::Up, which
+    // itself fires a click on browsers/engines that treat down+up on the same
+    // target as a click -- so the button-press side effect still runs.
+    let result = p.element_text("#result").unwrap();
+    assert_eq!(result, "clicked");
+}
+
+#[test]
+fn test_perform_actions_json_key_source_types_text() {
+    reset_and_open(FORM_HTML);
+    let p = page();
+
+    p.click_selector("#name-input").expect("click input failed");
+    let json = r#"[
+        {
+            "type": "key",
+            "actions": [
+                { "type": "keyDown", "value": "h" },
+                { "type": "keyUp", "value": "h" },
+                { "type": "keyDown", "value": "i" },
+                { "type": "keyUp", "value": "i" }
+            ]
+        }
+    ]"#;
+    p.perform_actions(json).expect("perform_actions failed");
+    p.wait(0.2);
+
+    let value = p
+        .evaluate("document.getElementById('name-input').value")
+        .unwrap();
+    assert!(
+        value.contains("hi"),
+        "input value should contain 'hi': {value}"
+    );
+}
+
+#[test]
+fn test_perform_actions_json_invalid_payload_errors() {
+    reset_and_open(FORM_HTML);
+    let err = page().perform_actions("not json").unwrap_err();
+    assert!(matches!(err, PageError::JsError(_)), "{err:?}");
+}
+
+// ---------------------------------------------------------------------------
+// Group 18: Init Scripts
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_add_init_script_runs_on_open() {
+    let p = page();
+    p.add_init_script("window.__injected = 'from-init-script';");
+    reset_and_open(BASIC_HTML);
+
+    let value = p.evaluate("window.__injected").unwrap();
+    assert!(
+        value.contains("from-init-script"),
+        "init script should have run: {value}"
+    );
+}
+
+#[test]
+fn test_remove_init_script_stops_it_from_running() {
+    let p = page();
+    let id = p.add_init_script("window.__should_not_run = true;");
+    assert!(p.remove_init_script(id), "remove should report success");
+    reset_and_open(BASIC_HTML);
+
+    let value = p.evaluate("window.__should_not_run").unwrap();
+    assert_eq!(value, "undefined", "removed script should not have run");
+}
+
+#[test]
+fn test_remove_init_script_unknown_id_returns_false() {
+    let p = page();
+    assert!(!p.remove_init_script(u32::MAX));
+}
+
+#[test]
+fn test_init_script_reapplies_on_script_driven_navigation() {
+    let p = page();
+    let id = p.add_init_script("window.__injected_nav = 'yes';");
+    reset_and_open(BASIC_HTML);
+    p.evaluate("window.__injected_nav = undefined").unwrap();
+
+    let target = data_url(LIST_HTML);
+    p.evaluate(&format!("window.location.href = '{target}'"))
+        .unwrap();
+    p.wait_for_selector(".item", 10)
+        .expect("script-driven navigation should complete");
+
+    let value = p.evaluate("window.__injected_nav").unwrap();
+    p.remove_init_script(id);
+    assert!(
+        value.contains("yes"),
+        "init script should re-run on a navigation the engine didn't initiate via open(): {value}"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Group 19: HAR Export
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_har_is_valid_log() {
+    reset_and_open(BASIC_HTML);
+
+    let har = page().har().expect("har failed");
+    assert!(har.contains(r#""version":"1.2""#), "missing HAR version: {har}");
+    assert!(har.contains(r#""entries":["#), "missing entries array: {har}");
+    assert!(har.contains(r#""startedDateTime""#), "missing timestamp: {har}");
+    assert!(har.contains(r#""method":"GET""#), "missing request method: {har}");
+    assert!(har.contains(r#""mimeType":"text/html""#), "missing mime type: {har}");
+}
+
+#[test]
+fn test_har_fulfilled_request_has_real_wait_timing() {
+    reset_and_open(BASIC_HTML);
+    let p = page();
+
+    p.add_route(
+        r#"{"pattern":"data:text/html","action":"fulfill","status":200,"headers":{},"body":""}"#,
+    )
+    .expect("add_route failed");
+    // Drain the initial, unfulfilled load so the HAR holds only the reload.
+    p.network_requests();
+    let _ = p.reload();
+    p.clear_routes().expect("clear_routes failed");
+
+    let har = p.har().expect("har failed");
+    assert!(
+        har.contains(r#""status":200"#),
+        "expected the fulfilled response status in: {har}"
+    );
+    assert!(
+        !har.contains(r#""wait":-1"#),
+        "fulfilled request should have a measured wait timing: {har}"
+    );
+}
+
+#[test]
+fn test_network_responses_and_get_response_body() {
+    reset_and_open(BASIC_HTML);
+    let p = page();
+
+    p.add_route(
+        r#"{"pattern":"data:text/html","action":"fulfill","status":200,"headers":{},"body":"aGVsbG8="}"#,
+    )
+    .expect("add_route failed");
+    let _ = p.reload();
+    p.clear_routes().expect("clear_routes failed");
+
+    let url = p.url().expect("url should be set after reload");
+    let body = p.get_response_body(&url);
+    assert_eq!(
+        body.as_deref(),
+        Some(b"hello".as_slice()),
+        "fulfilled response body should be recorded: {body:?}"
+    );
+
+    let responses = p.network_responses();
+    assert!(
+        responses.iter().any(|r| r.status == Some(200)),
+        "network_responses should include the fulfilled response: {responses:?}"
+    );
+}
+
+#[test]
+fn test_capture_response_bodies_runtime_toggle() {
+    reset_and_open(BASIC_HTML);
+    let p = page();
+
+    p.capture_response_bodies(false);
+    p.add_route(
+        r#"{"pattern":"data:text/html","action":"fulfill","status":200,"headers":{},"body":"aGVsbG8="}"#,
+    )
+    .expect("add_route failed");
+    let _ = p.reload();
+    p.clear_routes().expect("clear_routes failed");
+
+    let url = p.url().expect("url should be set after reload");
+    let body = p.get_response_body(&url);
+    assert_eq!(
+        body, None,
+        "body should not be recorded while capture is disabled: {body:?}"
+    );
+
+    // Re-enable for subsequent tests that rely on the singleton's default (see `page()`).
+    p.capture_response_bodies(true);
+}
+
+#[test]
+fn test_har_drains_network_requests() {
+    reset_and_open(BASIC_HTML);
+
+    let p = page();
+    let first = p.har().expect("har failed");
+    assert!(first.contains(r#""startedDateTime""#), "first har() should have entries");
+
+    let second = p.har().expect("har failed");
+    assert!(
+        second.contains(r#""entries":[]"#),
+        "second har() call should have no entries left to drain: {second}"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Group 20: Intercepted Request Audit Log
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_intercepted_requests_records_route_decision() {
+    reset_and_open(BASIC_HTML);
+
+    let p = page();
+    p.add_route(r#"{"pattern":"data:text/html","action":"block"}"#)
+        .expect("add_route failed");
+    // `add_route` only applies to requests made after it's registered, so reload to
+    // have the main-frame request actually go through the route.
+    let _ = p.reload();
+    p.clear_routes().expect("clear_routes failed");
+
+    let intercepted = p.intercepted_requests();
+    assert!(
+        intercepted.iter().any(|r| r.action == "block"),
+        "expected a blocked request in the audit log: {intercepted:?}"
+    );
+}
+
+#[test]
+fn test_intercepted_requests_drain() {
+    reset_and_open(BASIC_HTML);
+
+    let p = page();
+    p.add_route(r#"{"pattern":"data:text/html","action":"block"}"#)
+        .expect("add_route failed");
+    let _ = p.reload();
+    p.clear_routes().expect("clear_routes failed");
+
+    let first = p.intercepted_requests();
+    assert!(!first.is_empty(), "first drain should have entries");
+
+    let second = p.intercepted_requests();
+    assert!(second.is_empty(), "second drain should be empty");
+}
+
+// ---------------------------------------------------------------------------
+// Group 21: User-Agent and Extra HTTP Headers
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_set_user_agent_overrides_navigator() {
+    let p = page();
+    p.set_user_agent("TestBot/1.0");
+    reset_and_open(BASIC_HTML);
+
+    let ua = p.evaluate("navigator.userAgent").unwrap();
+    assert!(ua.contains("TestBot/1.0"), "navigator.userAgent: {ua}");
+}
+
+#[test]
+fn test_set_extra_http_headers_patches_fetch() {
+    let p = page();
+    let mut headers = std::collections::HashMap::new();
+    headers.insert("X-Test-Header".to_string(), "hello".to_string());
+    p.set_extra_http_headers(headers);
+    reset_and_open(BASIC_HTML);
+
+    // `extra_headers_script` replaces `window.fetch` with a wrapper that injects the
+    // configured headers; its source embeds the `extraHeaders` variable name.
+    let fetch_source = p.evaluate("window.fetch.toString()").unwrap();
+    assert!(
+        fetch_source.contains("extraHeaders"),
+        "fetch should have been monkey-patched: {fetch_source}"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Group 22: Element Handles
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_find_returns_handle() {
+    reset_and_open(BASIC_HTML);
+
+    let handle = page()
+        .find("#heading")
+        .expect("find failed")
+        .expect("should find #heading");
+    assert_eq!(handle.selector, "#heading");
+}
+
+#[test]
+fn test_find_not_found() {
+    reset_and_open(BASIC_HTML);
+
+    let handle = page().find("#nonexistent").expect("find failed");
+    assert!(handle.is_none());
+}
+
+#[test]
+fn test_find_all_returns_one_handle_per_match() {
+    reset_and_open(LIST_HTML);
+
+    let handles = page().find_all(".item").expect("find_all failed");
+    assert_eq!(handles.len(), 3);
+}
+
+#[test]
+fn test_find_all_no_matches_is_empty() {
+    reset_and_open(LIST_HTML);
+
+    let handles = page().find_all(".nonexistent").expect("find_all failed");
+    assert!(handles.is_empty());
+}
+
+#[test]
+fn test_handle_text_and_attribute_scoped_to_element() {
+    reset_and_open(LIST_HTML);
+    let p = page();
+
+    let handles = p.find_all(".item").expect("find_all failed");
+    assert_eq!(handles.len(), 3);
+
+    // Each handle stays bound to the element it was resolved from, not whichever
+    // `.item` a fresh `querySelector` would pick.
+    assert_eq!(p.handle_text(&handles[0]).unwrap(), "One");
+    assert_eq!(p.handle_text(&handles[1]).unwrap(), "Two");
+    assert_eq!(p.handle_text(&handles[2]).unwrap(), "Three");
+
+    assert_eq!(
+        p.handle_attribute(&handles[0], "class").unwrap(),
+        Some("item".to_string())
+    );
+    assert_eq!(
+        p.handle_attribute(&handles[0], "nonexistent").unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_handle_bounding_box() {
+    reset_and_open(BASIC_HTML);
+    let p = page();
+
+    let handle = p.find("#heading").unwrap().expect("should find #heading");
+    let rect = p.handle_bounding_box(&handle).expect("bounding_box failed");
+    assert!(rect.width > 0.0, "width should be positive: {}", rect.width);
+}
+
+#[test]
+fn test_handle_click_and_type_text() {
+    reset_and_open(FORM_HTML);
+    let p = page();
+
+    let handle = p
+        .find("#submit-btn")
+        .unwrap()
+        .expect("should find #submit-btn");
+    p.handle_click(&handle).expect("handle_click failed");
+    p.wait(0.3);
+
+    let result = p.element_text("#result").unwrap();
+    assert_eq!(result, "clicked");
+}
+
+#[test]
+fn test_handle_operation_on_stale_handle_reports_original_selector() {
+    reset_and_open(BASIC_HTML);
+    let p = page();
+
+    let handle = p.find("#heading").unwrap().expect("should find #heading");
+    reset_and_open(BASIC_HTML); // marker attribute doesn't survive a fresh navigation
+
+    match p.handle_text(&handle) {
+        Err(PageError::SelectorNotFound(sel)) => assert_eq!(sel, "#heading"),
+        other => panic!("expected SelectorNotFound, got: {other:?}"),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Group 23: Single-file Archiving
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_save_archive_adds_source_comment_by_default() {
+    reset_and_open(ARCHIVE_HTML);
+
+    let html = page().save_archive(0).expect("save_archive failed");
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(
+        html.contains("<!-- Archived from "),
+        "expected a source comment in: {html}"
+    );
+}
+
+#[test]
+fn test_save_archive_exclude_source_comment() {
+    reset_and_open(ARCHIVE_HTML);
+
+    let html = page()
+        .save_archive(archive_flags::EXCLUDE_SOURCE_COMMENT)
+        .expect("save_archive failed");
+    assert!(!html.contains("<!-- Archived from "));
+}
+
+#[test]
+fn test_save_archive_exclude_js_strips_script() {
+    reset_and_open(ARCHIVE_HTML);
+
+    let html = page()
+        .save_archive(archive_flags::EXCLUDE_JS)
+        .expect("save_archive failed");
+    assert!(!html.contains("<script"));
+}
+
+#[test]
+fn test_save_archive_exclude_images_strips_img() {
+    reset_and_open(ARCHIVE_HTML);
+
+    let html = page()
+        .save_archive(archive_flags::EXCLUDE_IMAGES)
+        .expect("save_archive failed");
+    assert!(!html.contains("<img"));
+}
+
+#[test]
+fn test_save_archive_default_keeps_images_and_scripts() {
+    reset_and_open(ARCHIVE_HTML);
+
+    let html = page().save_archive(0).expect("save_archive failed");
+    assert!(html.contains("<img"));
+    assert!(html.contains("<script"));
+}
+
+// ---------------------------------------------------------------------------
+// Group 24: Navigator/Viewport Overrides
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_set_navigator_override_patches_field() {
+    let p = page();
+    p.set_navigator_override("platform", "TestOS")
+        .expect("set_navigator_override failed");
+    reset_and_open(BASIC_HTML);
+
+    let platform = p.evaluate("navigator.platform").unwrap();
+    assert!(
+        platform.contains("TestOS"),
+        "navigator.platform: {platform}"
+    );
+}
+
+#[test]
+fn test_set_navigator_override_rejects_unknown_field() {
+    let p = page();
+    match p.set_navigator_override("bogusField", "x") {
+        Err(PageError::JsError(_)) => {}
+        other => panic!("expected JsError, got: {other:?}"),
+    }
+}
+
+#[test]
+fn test_set_viewport_patches_screen_and_device_pixel_ratio() {
+    let p = page();
+    p.set_viewport(800, 600, 3.0);
+    reset_and_open(BASIC_HTML);
+
+    let width = p.evaluate("screen.width").unwrap();
+    let ratio = p.evaluate("window.devicePixelRatio").unwrap();
+    assert_eq!(width, "800.0");
+    assert_eq!(ratio, "3.0");
+}
+
+#[test]
+fn test_set_emulation_patches_touch_and_mobile_overrides() {
+    let p = page();
+    reset_and_open(BASIC_HTML);
+
+    p.set_emulation(EmulationSettings {
+        width: 400,
+        height: 800,
+        device_scale_factor: 2.0,
+        is_mobile: true,
+        has_touch: true,
+    })
+    .expect("set_emulation failed");
+
+    let width = p.evaluate("screen.width").unwrap();
+    let ratio = p.evaluate("window.devicePixelRatio").unwrap();
+    let max_touch_points = p.evaluate("navigator.maxTouchPoints").unwrap();
+    assert_eq!(width, "400.0");
+    assert_eq!(ratio, "2.0");
+    assert_eq!(max_touch_points, "5.0");
+}
+
+#[test]
+fn test_emulate_iphone_x_applies_viewport_touch_and_user_agent() {
+    let p = page();
+    reset_and_open(BASIC_HTML);
+
+    p.emulate(DeviceDescriptor::iphone_x())
+        .expect("emulate failed");
+    // The user-agent half of `emulate` only takes effect from the next
+    // navigation onward, same as `set_user_agent`.
+    reset_and_open(BASIC_HTML);
+
+    let width = p.evaluate("screen.width").unwrap();
+    let ratio = p.evaluate("window.devicePixelRatio").unwrap();
+    let max_touch_points = p.evaluate("navigator.maxTouchPoints").unwrap();
+    let ua = p.evaluate("navigator.userAgent").unwrap();
+    assert_eq!(width, "375.0");
+    assert_eq!(ratio, "3.0");
+    assert_eq!(max_touch_points, "5.0");
+    assert!(ua.contains("iPhone"), "navigator.userAgent: {ua}");
+}
+
+#[test]
+fn test_emulate_pixel_5_applies_viewport_touch_and_user_agent() {
+    let p = page();
+    reset_and_open(BASIC_HTML);
+
+    p.emulate(DeviceDescriptor::pixel_5()).expect("emulate failed");
+    reset_and_open(BASIC_HTML);
+
+    let width = p.evaluate("screen.width").unwrap();
+    let ratio = p.evaluate("window.devicePixelRatio").unwrap();
+    let max_touch_points = p.evaluate("navigator.maxTouchPoints").unwrap();
+    let ua = p.evaluate("navigator.userAgent").unwrap();
+    assert_eq!(width, "393.0");
+    assert_eq!(ratio, "2.75");
+    assert_eq!(max_touch_points, "5.0");
+    assert!(ua.contains("Pixel 5"), "navigator.userAgent: {ua}");
+}
+
+#[test]
+fn test_emulate_ipad_applies_viewport_touch_and_user_agent() {
+    let p = page();
+    reset_and_open(BASIC_HTML);
+
+    p.emulate(DeviceDescriptor::ipad()).expect("emulate failed");
+    reset_and_open(BASIC_HTML);
+
+    let width = p.evaluate("screen.width").unwrap();
+    let ratio = p.evaluate("window.devicePixelRatio").unwrap();
+    let max_touch_points = p.evaluate("navigator.maxTouchPoints").unwrap();
+    let ua = p.evaluate("navigator.userAgent").unwrap();
+    assert_eq!(width, "810.0");
+    assert_eq!(ratio, "2.0");
+    assert_eq!(max_touch_points, "5.0");
+    assert!(ua.contains("iPad"), "navigator.userAgent: {ua}");
+}
+
+// ---------------------------------------------------------------------------
+// Group 25: In-page Text Search
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_find_text_counts_case_insensitive_matches() {
+    reset_and_open(FIND_HTML);
+
+    let count = page().find_text("the", 0).expect("find_text failed");
+    assert_eq!(count, 3, "expected 3 case-insensitive matches");
+}
+
+#[test]
+fn test_find_text_case_sensitive_matches_fewer() {
+    reset_and_open(FIND_HTML);
+
+    let count = page()
+        .find_text("the", find_flags::CASE_SENSITIVE)
+        .expect("find_text failed");
+    assert_eq!(count, 2, "expected 2 case-sensitive matches");
+}
+
+#[test]
+fn test_find_next_wraps_and_returns_rect() {
+    reset_and_open(FIND_HTML);
+
+    let count = page()
+        .find_text("the", find_flags::WRAP)
+        .expect("find_text failed");
+    assert_eq!(count, 3);
+
+    // find_text already scrolls/highlights the first match; advancing three times
+    // passes the second and third matches and wraps back around to the first.
+    page().find_next().expect("find_next failed");
+    page().find_next().expect("find_next failed");
+    let wrapped = page().find_next().expect("find_next failed");
+    assert!(wrapped.width > 0.0 && wrapped.height > 0.0);
+}
+
+#[test]
+fn test_find_next_without_search_errors() {
+    reset_and_open(FIND_HTML);
+
+    match page().find_next() {
+        Err(PageError::SelectorNotFound(_)) => {}
+        other => panic!("expected SelectorNotFound, got: {other:?}"),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Group 26: Route Method Matching
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_add_route_method_mismatch_does_not_apply() {
+    reset_and_open(BASIC_HTML);
+
+    let p = page();
+    p.add_route(r#"{"pattern":"data:text/html","method":"POST","action":"block"}"#)
+        .expect("add_route failed");
+    // The main-frame document request is a GET, so a POST-scoped block rule should
+    // not apply to it.
+    let ok = p.reload();
+    p.clear_routes().expect("clear_routes failed");
+
+    assert!(ok.is_ok(), "GET reload should not be blocked: {ok:?}");
+}
+
+#[test]
+fn test_add_route_method_match_applies() {
+    reset_and_open(BASIC_HTML);
+
+    let p = page();
+    p.add_route(r#"{"pattern":"data:text/html","method":"get","action":"block"}"#)
+        .expect("add_route failed");
+    let _ = p.reload();
+    p.clear_routes().expect("clear_routes failed");
+
+    let intercepted = p.intercepted_requests();
+    assert!(
+        intercepted.iter().any(|r| r.action == "block"),
+        "expected a blocked request in the audit log: {intercepted:?}"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Group 27: JS Dialog Messages
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_dialog_messages_captures_alert_and_confirm() {
+    reset_and_open(BASIC_HTML);
+    let p = page();
+    p.dialog_messages(); // Drain any leftovers from a prior test.
+
+    p.evaluate("alert('hello from test')").unwrap();
+    p.evaluate("confirm('are you sure?')").unwrap();
+
+    let dialogs = p.dialog_messages();
+    assert_eq!(dialogs.len(), 2, "expected 2 dialogs: {dialogs:?}");
+    assert_eq!(dialogs[0].kind, DialogKind::Alert);
+    assert_eq!(dialogs[0].message, "hello from test");
+    assert_eq!(dialogs[1].kind, DialogKind::Confirm);
+    assert_eq!(dialogs[1].message, "are you sure?");
+}
+
+#[test]
+fn test_dialog_messages_drain() {
+    reset_and_open(BASIC_HTML);
+    let p = page();
+    p.dialog_messages();
+
+    p.evaluate("alert('one')").unwrap();
+    let first = p.dialog_messages();
+    assert!(!first.is_empty(), "first drain should have a dialog");
+
+    let second = p.dialog_messages();
+    assert!(second.is_empty(), "second drain should be empty");
+}
+
+// ---------------------------------------------------------------------------
+// Group 28: Batch Element Info
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_query_all_returns_rect_text_html_and_attributes() {
+    reset_and_open(BASIC_HTML);
+    let p = page();
+
+    let infos = p.query_all("h1, p, a").expect("query_all failed");
+    assert_eq!(infos.len(), 3, "expected 3 matches: {infos:?}");
+
+    let heading = &infos[0];
+    assert_eq!(heading.text, "Hello World");
+    assert!(heading.outer_html.contains("Hello World"));
+    assert_eq!(
+        heading.attributes.get("id").map(String::as_str),
+        Some("heading")
+    );
+    assert_eq!(
+        heading.attributes.get("data-testid").map(String::as_str),
+        Some("main-heading")
+    );
+    assert!(heading.rect.width > 0.0);
+
+    assert_eq!(infos[1].text, "Some paragraph text");
+    assert_eq!(infos[2].text, "Example Link");
+}
+
+#[test]
+fn test_query_all_no_match_returns_empty_vec() {
+    reset_and_open(BASIC_HTML);
+    let infos = page().query_all("#nope").expect("query_all failed");
+    assert!(infos.is_empty());
+}
+
+#[test]
+fn test_element_info_returns_first_match() {
+    reset_and_open(BASIC_HTML);
+    let info = page()
+        .element_info("#heading")
+        .expect("element_info failed");
+    assert_eq!(info.text, "Hello World");
+    assert_eq!(
+        info.attributes.get("class").map(String::as_str),
+        Some("main")
+    );
+}
+
+#[test]
+fn test_element_info_no_match_is_selector_not_found() {
+    reset_and_open(BASIC_HTML);
+    let err = page().element_info("#nope").unwrap_err();
+    assert!(matches!(err, PageError::SelectorNotFound(_)), "{err:?}");
+}
+
+// ---------------------------------------------------------------------------
+// Group 29: PDF `@page` Size Resolution
+// ---------------------------------------------------------------------------
+
+const PAGE_SIZE_HTML: &str = "\
+<html><head><title>Page Size</title>\
+<style>@page { size: 5IN 5IN; }</style>\
+</head><body><h1>Paged</h1></body></html>";
+
+fn pdf_media_box(pdf: &[u8]) -> String {
+    let pdf = String::from_utf8_lossy(pdf);
+    let start = pdf.find("/MediaBox").expect("no /MediaBox in PDF");
+    let end = pdf[start..].find(']').expect("unterminated /MediaBox") + start + 1;
+    pdf[start..end].to_string()
+}
+
+#[test]
+fn test_print_to_pdf_prefers_css_page_size_with_uppercase_units() {
+    reset_and_open(PAGE_SIZE_HTML);
+    let pdf = page()
+        .print_to_pdf(PdfOptions {
+            prefer_css_page_size: true,
+            ..Default::default()
+        })
+        .expect("print_to_pdf failed");
+
+    // 5in square at 72pt/in -- confirms the uppercase "IN" units in the @page
+    // rule were parsed rather than silently rejected for case mismatch.
+    assert_eq!(pdf_media_box(&pdf), "/MediaBox [0 0 360.00 360.00]");
+}
+
+#[test]
+fn test_print_to_pdf_ignores_css_page_size_by_default() {
+    reset_and_open(PAGE_SIZE_HTML);
+    let pdf = page()
+        .print_to_pdf(PdfOptions::default())
+        .expect("print_to_pdf failed");
+
+    // Default US Letter paper size, unaffected by the page's own @page rule.
+    assert_eq!(pdf_media_box(&pdf), "/MediaBox [0 0 612.00 792.00]");
+}
+
+// ---------------------------------------------------------------------------
+// Group 30: Exposed Functions
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_expose_function_resolves_page_side_promise_with_reply() {
+    reset_and_open(BASIC_HTML);
+    let p = page();
+
+    p.expose_function("greet", |payload| Some(format!("hello {payload}")));
+    p.evaluate(
+        "window.greet('world').then(function(r) { window.__result = r; })",
+    )
+    .expect("evaluate failed");
+    p.wait_for_condition("window.__result !== undefined", 10)
+        .expect("promise should resolve");
+
+    let result = p.evaluate("window.__result").unwrap();
+    assert_eq!(result, "\"hello world\"");
+}
+
+#[test]
+fn test_expose_function_none_reply_resolves_undefined() {
+    reset_and_open(BASIC_HTML);
+    let p = page();
+
+    p.expose_function("note", |_payload| None);
+    p.evaluate(
+        "window.note('x').then(function(r) { window.__done = true; window.__result = r; })",
+    )
+    .expect("evaluate failed");
+    p.wait_for_condition("window.__done === true", 10)
+        .expect("promise should resolve");
+
+    let result = p.evaluate("typeof window.__result").unwrap();
+    assert_eq!(result, "\"undefined\"");
+}
+
+#[test]
+fn test_expose_function_concurrent_calls_do_not_cross_wire() {
+    reset_and_open(BASIC_HTML);
+    let p = page();
+
+    // Echo the payload back so each call's reply can be checked against its own
+    // argument -- if the per-call promise IDs were mixed up, one of these would
+    // come back with the other call's payload instead.
+    p.expose_function("echo", |payload| Some(payload));
+    p.evaluate(
+        "Promise.all([window.echo('one'), window.echo('two'), window.echo('three')])\
+         .then(function(r) { window.__results = r; })",
+    )
+    .expect("evaluate failed");
+    p.wait_for_condition("window.__results !== undefined", 10)
+        .expect("all promises should resolve");
+
+    let result = p.evaluate("JSON.stringify(window.__results)").unwrap();
+    assert_eq!(result, "\"[\\\"one\\\",\\\"two\\\",\\\"three\\\"]\"");
+}
+
+#[test]
+fn test_expose_function_replaces_previous_handler_of_same_name() {
+    reset_and_open(BASIC_HTML);
+    let p = page();
+
+    let calls = Arc::new(Mutex::new(0));
+    let calls_clone = Arc::clone(&calls);
+    p.expose_function("counter", move |_payload| {
+        *calls_clone.lock().unwrap() += 1;
+        Some("first".to_string())
+    });
+    p.expose_function("counter", |_payload| Some("second".to_string()));
+
+    p.evaluate("window.counter('x').then(function(r) { window.__result = r; })")
+        .expect("evaluate failed");
+    p.wait_for_condition("window.__result !== undefined", 10)
+        .expect("promise should resolve");
+
+    let result = p.evaluate("window.__result").unwrap();
+    assert_eq!(result, "\"second\"");
+    // The replaced handler must never have run.
+    assert_eq!(*calls.lock().unwrap(), 0);
+}
+
+#[test]
+fn test_expose_function_spoofed_pseudo_url_request_is_ignored() {
+    reset_and_open(BASIC_HTML);
+    let p = page();
+
+    let calls = Arc::new(Mutex::new(0));
+    let calls_clone = Arc::clone(&calls);
+    p.expose_function("counter", move |_payload| {
+        *calls_clone.lock().unwrap() += 1;
+        Some("real".to_string())
+    });
+
+    // A page script hitting the pseudo-URL directly (bypassing the installed
+    // `window.counter` shim) must not be able to trigger the handler for a
+    // function it doesn't name, nor crash the page -- only the exact shim-built
+    // URL for a registered function does anything.
+    p.evaluate(
+        "var img = new Image(); img.src = 'https://__scraper_binding__/call?fn=nope&id=0&payload=%22x%22';",
+    )
+    .expect("evaluate failed");
+    p.wait(0.2);
+
+    assert_eq!(*calls.lock().unwrap(), 0);
+}
+
+// ---------------------------------------------------------------------------
+// Group 31: Reader Mode / EPUB Export
+// ---------------------------------------------------------------------------
+
+const ARTICLE_NOISE_HTML: &str = "\
+<html><head><title>Real Article</title></head><body>\
+<nav id=\"nav\"><a href=\"/a\">Home</a><a href=\"/b\">About</a><a href=\"/c\">Contact</a></nav>\
+<aside class=\"sidebar\"><a href=\"/ad1\">Ad</a><a href=\"/ad2\">Ad</a><a href=\"/ad3\">Ad</a></aside>\
+<article class=\"article-content\">\
+<h1>A Real Headline</h1>\
+<p>This is the first paragraph of the genuine article body, long enough to \
+score well above the surrounding navigation and sidebar noise on pure text \
+density alone, which is exactly the signal this extractor relies on.</p>\
+<p>A second paragraph continues the same article, adding more real prose so \
+the content block's link-free text clearly outweighs the link-heavy chrome \
+around it.</p>\
+</article>\
+<div id=\"comments\" class=\"comments\"><a href=\"/u1\">troll</a> said: meh</div>\
+</body></html>";
+
+fn unique_temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!(
+        "servo-scraper-test-{}-{name}",
+        std::process::id()
+    ))
+}
+
+#[test]
+fn test_extract_article_picks_content_over_nav_and_sidebar_noise() {
+    reset_and_open(ARTICLE_NOISE_HTML);
+    let article = page().extract_article().expect("extract_article failed");
+
+    assert!(
+        article.content_html.contains("A Real Headline"),
+        "content_html: {}",
+        article.content_html
+    );
+    assert!(article.text.contains("genuine article body"));
+    assert!(
+        !article.content_html.contains("Home"),
+        "nav leaked into extracted content: {}",
+        article.content_html
+    );
+    assert!(
+        !article.content_html.contains("troll"),
+        "comments leaked into extracted content: {}",
+        article.content_html
+    );
+}
+
+#[test]
+fn test_save_epub_produces_zip_with_uncompressed_mimetype_first_entry() {
+    reset_and_open(ARTICLE_NOISE_HTML);
+    let path = unique_temp_path("epub.epub");
+    let path_str = path.to_str().expect("temp path must be utf-8");
+
+    page().save_epub(path_str).expect("save_epub failed");
+
+    let file = std::fs::File::open(&path).expect("epub file should exist");
+    let mut zip = zip::ZipArchive::new(file).expect("epub should be a valid zip");
+
+    let mut mimetype = zip.by_index(0).expect("zip should have a first entry");
+    assert_eq!(mimetype.name(), "mimetype");
+    assert_eq!(
+        mimetype.compression(),
+        zip::CompressionMethod::Stored,
+        "mimetype entry must be stored uncompressed"
+    );
+    let mut contents = String::new();
+    std::io::Read::read_to_string(&mut mimetype, &mut contents).unwrap();
+    assert_eq!(contents, "application/epub+zip");
+    drop(mimetype);
+
+    let article_xhtml = {
+        let mut entry = zip
+            .by_name("OEBPS/article.xhtml")
+            .expect("epub should contain the article body");
+        let mut s = String::new();
+        std::io::Read::read_to_string(&mut entry, &mut s).unwrap();
+        s
+    };
+    assert!(article_xhtml.contains("A Real Headline"));
+
+    std::fs::remove_file(&path).ok();
+}
+
+// ---------------------------------------------------------------------------
+// Group 32: Markdown Conversion
+// ---------------------------------------------------------------------------
+
+const MARKDOWN_HTML: &str = "\
+<html><body>\
+<h1>Title</h1>\
+<p>This is <strong>bold</strong> and <em>italic</em> text with a \
+<a href=\"https://example.com/page\">link</a>.</p>\
+<ul><li>One</li><li>Two</li></ul>\
+</body></html>";
+
+const MARKDOWN_SCOPED_HTML: &str = "\
+<html><body>\
+<nav><a href=\"/x\">Nav link</a></nav>\
+<div id=\"scoped\"><h2>Scoped</h2><p>Only this part.</p></div>\
+</body></html>";
+
+#[test]
+fn test_markdown_converts_headings_inline_marks_links_and_lists() {
+    reset_and_open(MARKDOWN_HTML);
+    let md = page().markdown(None).expect("markdown failed");
+    assert_eq!(
+        md,
+        "# Title\n\nThis is **bold** and _italic_ text with a \
+         [link](https://example.com/page).\n\n- One\n- Two\n"
+    );
+}
+
+#[test]
+fn test_markdown_scoped_to_selector() {
+    reset_and_open(MARKDOWN_SCOPED_HTML);
+    let md = page()
+        .markdown(Some("#scoped"))
+        .expect("markdown failed");
+    assert_eq!(md, "## Scoped\n\nOnly this part.\n");
+    assert!(!md.contains("Nav link"));
+}
+
+#[test]
+fn test_markdown_selector_not_found() {
+    reset_and_open(MARKDOWN_SCOPED_HTML);
+    let err = page().markdown(Some("#nope")).unwrap_err();
+    assert!(matches!(err, PageError::SelectorNotFound(_)), "{err:?}");
+}
+
+// ---------------------------------------------------------------------------
+// Group 33: Screenshot Diffing
+// ---------------------------------------------------------------------------
+
+const DIFF_BASE_HTML: &str = "\
+<html><body style=\"margin:0;background:#ffffff;\">\
+<div style=\"width:800px;height:600px;background:#ffffff;\"></div>\
+</body></html>";
+
+const DIFF_CHANGED_HTML: &str = "\
+<html><body style=\"margin:0;background:#ffffff;\">\
+<div style=\"width:800px;height:600px;background:#ffffff;\">\
+<div style=\"position:absolute;top:100px;left:100px;width:200px;height:200px;background:#ff0000;\"></div>\
+</div>\
+</body></html>";
+
+#[test]
+fn test_screenshot_diff_identical_screenshots_have_no_diff() {
+    reset_and_open(DIFF_BASE_HTML);
+    let baseline = page().screenshot().expect("screenshot failed");
+
+    let diff = page()
+        .screenshot_diff(&baseline)
+        .expect("screenshot_diff failed");
+    assert_eq!(diff.diff_pixels, 0, "identical screenshots should not differ");
+    assert_eq!(diff.total_pixels, 800 * 600);
+    assert_eq!(&diff.diff_image[..4], &PNG_MAGIC, "diff_image is not a valid PNG");
+}
+
+#[test]
+fn test_screenshot_diff_detects_visual_change() {
+    reset_and_open(DIFF_BASE_HTML);
+    let baseline = page().screenshot().expect("screenshot failed");
+
+    reset_and_open(DIFF_CHANGED_HTML);
+    let diff = page()
+        .screenshot_diff(&baseline)
+        .expect("screenshot_diff failed");
+
+    // A solid 200x200 red block dropped onto an otherwise unchanged white
+    // page should register as a hard edge, not anti-aliasing noise, so the
+    // diff count should land close to (not wildly under) its pixel area.
+    assert!(
+        diff.diff_pixels > 30_000,
+        "expected a large diff for the injected red block, got {}",
+        diff.diff_pixels
+    );
+    assert!(diff.diff_pixels <= diff.total_pixels);
+}
+
+// ---------------------------------------------------------------------------
+// Group 34: Popup Events
+// ---------------------------------------------------------------------------
+
+const POPUP_HTML: &str = "\
+<html><body>\
+<button id=\"open-popup\" onclick=\"window.open('data:text/html,%3Ch1%3EPopup%3C%2Fh1%3E', '_blank')\">Open</button>\
+</body></html>";
+
+#[test]
+fn test_popup_events_reports_opened_popup_under_capture_policy() {
+    reset();
+    let p = page();
+    p.set_popup_policy(PopupPolicy::Capture);
+    p.open(&data_url(POPUP_HTML)).expect("open failed");
+
+    let events = p.popup_events().expect("popup_events failed");
+    p.click_selector("#open-popup").expect("click failed");
+
+    let event = events
+        .recv_timeout(Duration::from_secs(10))
+        .expect("expected a PopupEvent to arrive");
+    assert_eq!(event.kind, PopupEventKind::Opened);
+    assert!(event.page_id.is_some(), "captured popup should have a page_id");
+    assert!(event.url.contains("Popup"), "event.url: {}", event.url);
+
+    p.set_popup_policy(PopupPolicy::Block);
+}
+
+#[test]
+fn test_popup_events_reports_no_page_id_under_redirect_policy() {
+    reset();
+    let p = page();
+    p.set_popup_policy(PopupPolicy::Redirect);
+    p.open(&data_url(POPUP_HTML)).expect("open failed");
+
+    let events = p.popup_events().expect("popup_events failed");
+    p.click_selector("#open-popup").expect("click failed");
+
+    // Redirect never creates a second page -- it navigates the opener's own
+    // WebView to the popup URL instead -- so the event reports no page_id.
+    let event = events
+        .recv_timeout(Duration::from_secs(10))
+        .expect("expected a PopupEvent to arrive under Redirect policy");
+    assert_eq!(event.kind, PopupEventKind::Opened);
+    assert!(
+        event.page_id.is_none(),
+        "a redirected popup never creates a page, so it shouldn't have a page_id"
+    );
+
+    p.set_popup_policy(PopupPolicy::Block);
+}
+
+#[test]
+fn test_popup_events_none_under_block_policy() {
+    reset();
+    let p = page();
+    p.set_popup_policy(PopupPolicy::Block);
+    p.open(&data_url(POPUP_HTML)).expect("open failed");
+
+    let events = p.popup_events().expect("popup_events failed");
+    p.click_selector("#open-popup").expect("click failed");
+
+    // Block drops the request before a WebView is even built, so no event
+    // fires at all -- unlike Redirect, which still reports an Opened event
+    // with no page_id.
+    assert!(
+        events.recv_timeout(Duration::from_millis(500)).is_err(),
+        "Block policy should not emit a PopupEvent"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Group 35: Download Capture
+// ---------------------------------------------------------------------------
+
+const DOWNLOAD_DATA_URI_HTML: &str = "\
+<html><body>\
+<a id=\"dl\" href=\"data:text/csv,a,b,c\" download=\"export.csv\">Export</a>\
+</body></html>";
+
+const DOWNLOAD_PLAIN_LINK_HTML: &str = "\
+<html><body>\
+<a id=\"dl\" href=\"https://example.test/export\">Export</a>\
+</body></html>";
+
+const DOWNLOAD_NORMAL_NAV_HTML: &str = "\
+<html><body>\
+<a id=\"nav\" href=\"https://example.test/next\">Next</a>\
+</body></html>";
+
+#[test]
+fn test_download_capture_data_uri_with_download_attribute() {
+    reset_and_open(DOWNLOAD_DATA_URI_HTML);
+    let p = page();
+    p.set_download_capture(true).expect("set_download_capture failed");
+
+    p.click_selector("#dl").expect("click failed");
+    p.wait_for_downloads(1, 10).expect("wait_for_downloads failed");
+
+    let downloads = p.get_downloads().expect("get_downloads failed");
+    assert!(
+        downloads.contains(r#""suggested_filename":"export.csv""#),
+        "expected the `download` attribute's filename: {downloads}"
+    );
+    assert!(
+        downloads.contains(r#""url":"data:text/csv,a,b,c""#),
+        "expected the data: URI as the download's url: {downloads}"
+    );
+
+    p.set_download_capture(false).expect("set_download_capture failed");
+}
+
+#[test]
+fn test_download_capture_content_disposition_header_on_plain_link() {
+    reset_and_open(DOWNLOAD_PLAIN_LINK_HTML);
+    let p = page();
+    p.set_download_capture(true).expect("set_download_capture failed");
+
+    // Simulate a server responding to a plain `<a href>` with a
+    // `Content-Disposition: attachment` header -- nothing about the link itself
+    // (no `download` attribute, not a blob:/data: URI) marks it as a download.
+    p.add_route(
+        r#"{"pattern":"https://example.test/export","action":"fulfill","status":200,
+            "headers":{"Content-Disposition":"attachment; filename=\"report.pdf\""},
+            "body":"cmVwb3J0"}"#,
+    )
+    .expect("add_route failed");
+
+    p.click_selector("#dl").expect("click failed");
+    p.wait_for_downloads(1, 10).expect("wait_for_downloads failed");
+    p.clear_routes().expect("clear_routes failed");
+
+    let downloads = p.get_downloads().expect("get_downloads failed");
+    assert!(
+        downloads.contains(r#""suggested_filename":"report.pdf""#),
+        "expected the filename from the Content-Disposition header: {downloads}"
+    );
+    assert!(
+        downloads.contains(r#""url":"https://example.test/export""#),
+        "expected the link's own url on the captured download: {downloads}"
+    );
+
+    p.set_download_capture(false).expect("set_download_capture failed");
+}
+
+#[test]
+fn test_download_capture_ignores_plain_link_without_attachment_header() {
+    reset_and_open(DOWNLOAD_NORMAL_NAV_HTML);
+    let p = page();
+    p.set_download_capture(true).expect("set_download_capture failed");
+
+    // An ordinary page response (no Content-Disposition at all) should navigate
+    // normally instead of being captured as a download.
+    p.add_route(
+        r#"{"pattern":"https://example.test/next","action":"fulfill","status":200,
+            "headers":{"Content-Type":"text/html"},"body":"PGgxPk5leHQ8L2gxPg=="}"#,
+    )
+    .expect("add_route failed");
+
+    p.click_selector("#nav").expect("click failed");
+    p.wait_for_selector("h1", 10).expect("wait_for_selector failed");
+    p.clear_routes().expect("clear_routes failed");
+
+    let downloads = p.get_downloads().expect("get_downloads failed");
+    assert_eq!(
+        downloads, "[]",
+        "a non-attachment response should navigate, not be captured: {downloads}"
+    );
+
+    p.set_download_capture(false).expect("set_download_capture failed");
+}
+
+#[test]
+fn test_save_download_writes_bytes_to_disk() {
+    reset_and_open(DOWNLOAD_DATA_URI_HTML);
+    let p = page();
+    p.set_download_capture(true).expect("set_download_capture failed");
+
+    p.click_selector("#dl").expect("click failed");
+    p.wait_for_downloads(1, 10).expect("wait_for_downloads failed");
+
+    let dest = unique_temp_path("export.csv");
+    p.save_download(0, dest.to_str().expect("path should be utf8"))
+        .expect("save_download failed");
+    let bytes = std::fs::read(&dest).expect("saved download should exist on disk");
+    assert_eq!(bytes, b"a,b,c");
+    let _ = std::fs::remove_file(&dest);
+
+    p.set_download_capture(false).expect("set_download_capture failed");
+}